@@ -0,0 +1,380 @@
+use anyhow::Result;
+use std::fmt::Write as FmtWrite;
+
+use crate::git;
+use crate::release::Version;
+use crate::utils::{self, ConventionalHeader, COMMIT_TYPES};
+
+/// CHANGELOG 文件名
+pub const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+/// CHANGELOG 文件标题
+const CHANGELOG_HEADER: &str = "# Changelog";
+
+/// 未发布变更的分节标题
+const UNRELEASED_TITLE: &str = "## Unreleased";
+
+/// 用于生成 CHANGELOG 的提交
+struct ChangelogCommit {
+    hash: String,
+    header: ConventionalHeader,
+    body: Vec<String>,
+    breaking_note: Option<String>,
+}
+
+/// 解析提交消息，非 Conventional Commits 格式、发布提交和 fixup/WIP 提交返回None
+fn parse_commit(hash: &str, message: &str) -> Option<ChangelogCommit> {
+    let message = utils::strip_skip_ci(message);
+
+    let mut lines = message.lines();
+    let header = utils::parse_conventional_header(lines.next()?.trim())?;
+
+    if header.commit_type == "chore" && header.scope.as_deref() == Some("release") {
+        return None;
+    }
+
+    let mut body = Vec::new();
+    let mut breaking_note = None;
+
+    for line in lines.map(str::trim).filter(|l| !l.is_empty()) {
+        match utils::parse_trailer(line) {
+            Some((key, note)) if key == utils::BREAKING_CHANGE_KEY => breaking_note = Some(note),
+            Some(_) => {}
+            None => {
+                let text = line.trim_start_matches("- ");
+                if !text.starts_with("This reverts commit") && !text.starts_with("(cherry picked from commit") {
+                    body.push(text.to_string());
+                }
+            }
+        }
+    }
+
+    Some(ChangelogCommit {
+        hash: hash.to_string(),
+        header,
+        body,
+        breaking_note,
+    })
+}
+
+/// 渲染一条 CHANGELOG 条目
+fn render_entry(output: &mut String, commit: &ChangelogCommit, description: &str, repo_url: Option<&str>) {
+    let scope = commit
+        .header
+        .scope
+        .as_ref()
+        .map_or(String::new(), |scope| format!("**{}:** ", scope));
+    let short_hash = &commit.hash[..commit.hash.len().min(7)];
+    let link = match repo_url {
+        Some(url) => format!("[{}]({}/commit/{})", short_hash, url, commit.hash),
+        None => short_hash.to_string(),
+    };
+
+    _ = writeln!(output, "- {}{} ({})", scope, description, link);
+}
+
+/// 渲染一个版本的 CHANGELOG 分节
+///
+/// # 参数
+/// * `title` - 分节标题 (例如: ## v1.2.0 (2025-03-30))
+/// * `messages` - 该版本包含的 (哈希, 提交消息) 列表，按时间从新到旧排列
+/// * `repo_url` - 远程仓库网页地址，用于生成提交链接
+///
+/// # 返回值
+/// 返回渲染后的 Markdown，没有可记录的提交时返回None
+fn render_release(title: &str, messages: &[(String, String)], repo_url: Option<&str>) -> Option<String> {
+    let commits: Vec<ChangelogCommit> = messages
+        .iter()
+        .filter_map(|(hash, message)| parse_commit(hash, message))
+        .collect();
+
+    if commits.is_empty() {
+        return None;
+    }
+
+    let mut output = String::new();
+    _ = writeln!(output, "{}", title);
+
+    // 破坏性变更单独列出
+    let breaking: Vec<&ChangelogCommit> = commits
+        .iter()
+        .filter(|c| c.header.breaking || c.breaking_note.is_some())
+        .collect();
+    if !breaking.is_empty() {
+        _ = writeln!(output);
+        _ = writeln!(output, "### ⚠ BREAKING CHANGES");
+        _ = writeln!(output);
+        for commit in breaking {
+            let note = commit.breaking_note.as_deref().unwrap_or(&commit.header.description);
+            render_entry(&mut output, commit, note, repo_url);
+        }
+    }
+
+    // 按 COMMIT_TYPES 的顺序输出各类型的分节
+    for (commit_type, _, section) in COMMIT_TYPES {
+        let entries: Vec<&ChangelogCommit> = commits
+            .iter()
+            .filter(|c| c.header.commit_type == *commit_type)
+            .collect();
+        if entries.is_empty() {
+            continue;
+        }
+
+        _ = writeln!(output);
+        _ = writeln!(output, "### {}", section);
+        _ = writeln!(output);
+        for commit in entries {
+            render_entry(&mut output, commit, &commit.header.description, repo_url);
+            for line in &commit.body {
+                _ = writeln!(output, "  - {}", line);
+            }
+        }
+    }
+
+    Some(output)
+}
+
+/// 生成未发布变更 (最近的版本标签之后的提交) 的分节
+///
+/// # 参数
+/// * `tags` - 版本标签列表，按版本从新到旧排列
+/// * `repo_url` - 远程仓库网页地址
+///
+/// # 返回值
+/// 返回渲染后的 Markdown，没有未发布的变更时返回None
+fn render_unreleased(tags: &[String], repo_url: Option<&str>) -> Result<Option<String>> {
+    let range = match tags.first() {
+        Some(tag) => format!("{}..HEAD", tag),
+        None => "HEAD".to_string(),
+    };
+
+    Ok(render_release(UNRELEASED_TITLE, &git::commit_messages(&range)?, repo_url))
+}
+
+/// 生成指定版本标签的分节，包含上一个版本标签之后到该标签的提交
+///
+/// # 参数
+/// * `tags` - 版本标签列表，按版本从新到旧排列
+/// * `index` - 要生成的标签在列表中的位置
+/// * `repo_url` - 远程仓库网页地址
+///
+/// # 返回值
+/// 返回渲染后的 Markdown，该版本没有可记录的提交时返回None
+fn render_tag(tags: &[String], index: usize, repo_url: Option<&str>) -> Result<Option<String>> {
+    let tag = &tags[index];
+    let range = match tags.get(index + 1) {
+        Some(previous) => format!("{}..{}", previous, tag),
+        None => tag.clone(),
+    };
+    let title = format!("## {} ({})", tag, git::commit_date(tag)?);
+
+    Ok(render_release(&title, &git::commit_messages(&range)?, repo_url))
+}
+
+/// 根据完整的提交历史重新生成 CHANGELOG，按版本标签分组
+///
+/// # 参数
+/// * `repo_url` - 远程仓库网页地址
+///
+/// # 返回值
+/// 返回完整的 CHANGELOG 内容
+pub fn render_full(repo_url: Option<&str>) -> Result<String> {
+    let tags = git::version_tags()?;
+
+    let mut sections = Vec::new();
+    sections.extend(render_unreleased(&tags, repo_url)?);
+    for index in 0..tags.len() {
+        sections.extend(render_tag(&tags, index, repo_url)?);
+    }
+
+    Ok(prepend_sections("", &sections))
+}
+
+/// 增量更新已有的 CHANGELOG
+///
+/// 为文件中最新的版本分节之后创建的每个版本标签 (例如 `og release` 之后) 生成分节，
+/// 并重新生成未发布变更的分节，没有未发布的变更时去掉该分节。
+///
+/// # 参数
+/// * `existing` - 已有的 CHANGELOG 内容
+/// * `repo_url` - 远程仓库网页地址
+///
+/// # 返回值
+/// 返回更新后的 CHANGELOG 内容
+pub fn render_incremental(existing: &str, repo_url: Option<&str>) -> Result<String> {
+    let tags = git::version_tags()?;
+    let recorded = latest_recorded_version(existing);
+
+    let mut sections = Vec::new();
+    sections.extend(render_unreleased(&tags, repo_url)?);
+    for (index, tag) in tags.iter().enumerate() {
+        if recorded.is_some_and(|recorded| Version::parse(tag).is_some_and(|version| version <= recorded)) {
+            break;
+        }
+        sections.extend(render_tag(&tags, index, repo_url)?);
+    }
+
+    Ok(prepend_sections(existing, &sections))
+}
+
+/// 读取 CHANGELOG 中已记录的最新版本 (`## v1.2.0 (...)` 形式的分节标题)
+fn latest_recorded_version(existing: &str) -> Option<Version> {
+    existing
+        .lines()
+        .filter_map(|line| line.strip_prefix("## "))
+        .filter_map(|title| Version::parse(title.split_whitespace().next()?))
+        .max()
+}
+
+/// 将新的分节插入到已有 CHANGELOG 的标题之后，并去掉已存在的未发布分节
+///
+/// # 参数
+/// * `existing` - 已有的 CHANGELOG 内容
+/// * `sections` - 新的分节，按从新到旧排列
+///
+/// # 返回值
+/// 返回更新后的 CHANGELOG 内容
+fn prepend_sections(existing: &str, sections: &[String]) -> String {
+    let lines: Vec<&str> = existing.lines().collect();
+
+    // 去掉已存在的未发布分节
+    let mut kept: Vec<&str> = Vec::new();
+    let mut in_unreleased = false;
+    for line in &lines {
+        if line.starts_with("## ") {
+            in_unreleased = line.trim() == UNRELEASED_TITLE;
+        }
+        if !in_unreleased {
+            kept.push(line);
+        }
+    }
+
+    // 标题之后插入新分节
+    let header_end = kept
+        .iter()
+        .position(|line| line.starts_with("# "))
+        .map_or(0, |index| index + 1);
+
+    let mut output = String::new();
+    if header_end == 0 {
+        _ = writeln!(output, "{}", CHANGELOG_HEADER);
+    } else {
+        for line in &kept[..header_end] {
+            _ = writeln!(output, "{}", line);
+        }
+    }
+
+    for section in sections {
+        _ = write!(output, "\n{}", section);
+    }
+
+    let rest: Vec<&str> = kept[header_end..]
+        .iter()
+        .copied()
+        .skip_while(|line| line.trim().is_empty())
+        .collect();
+    if !rest.is_empty() {
+        _ = writeln!(output);
+        for line in rest {
+            _ = writeln!(output, "{}", line);
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items.iter().map(|(hash, message)| (hash.to_string(), message.to_string())).collect()
+    }
+
+    #[test]
+    fn renders_sections_by_type_with_breaking_changes_first() {
+        let section = render_release(
+            UNRELEASED_TITLE,
+            &messages(&[
+                ("bbbbbbbbbb", "fix(parser): 空行解析\n- 跳过空行"),
+                ("aaaaaaaaaa", "feat!: 新配置格式\n\nBREAKING CHANGE: 旧配置不再支持"),
+                ("cccccccccc", "更新依赖"),
+            ]),
+            Some("https://example.com/repo"),
+        )
+        .unwrap();
+
+        let breaking = section.find("### ⚠ BREAKING CHANGES").unwrap();
+        let features = section.find("- 新配置格式 ([aaaaaaa](https://example.com/repo/commit/aaaaaaaaaa))").unwrap();
+        assert!(breaking < features);
+        assert!(section.contains("- 旧配置不再支持 ([aaaaaaa]"));
+        assert!(section.contains("- **parser:** 空行解析 ([bbbbbbb]"));
+        assert!(section.contains("  - 跳过空行"));
+        assert!(!section.contains("更新依赖"));
+    }
+
+    #[test]
+    fn skips_release_commits_and_revert_lines() {
+        assert!(render_release(UNRELEASED_TITLE, &messages(&[("a", "chore(release): v1.0.0")]), None).is_none());
+
+        let commit = parse_commit("a", "revert: feat: 登录\n- This reverts commit 1234.\n\nThis reverts commit 1234.").unwrap();
+        assert!(commit.body.is_empty());
+    }
+
+    #[test]
+    fn prepend_sections_is_idempotent() {
+        let existing = "# Changelog\n\n## v1.0.0 (2025-01-01)\n\n### 新功能\n\n- 初始版本 (aaaaaaa)\n";
+        let section = "## Unreleased\n\n### 问题修复\n\n- 修复崩溃 (bbbbbbb)\n";
+
+        let once = prepend_sections(existing, &[section.to_string()]);
+        let twice = prepend_sections(&once, &[section.to_string()]);
+
+        assert_eq!(once, twice);
+        assert!(once.starts_with("# Changelog\n\n## Unreleased\n"));
+        assert!(once.ends_with("## v1.0.0 (2025-01-01)\n\n### 新功能\n\n- 初始版本 (aaaaaaa)\n"));
+    }
+
+    #[test]
+    fn prepend_sections_replaces_stale_section() {
+        let existing = "# Changelog\n\n## Unreleased\n\n- 旧条目 (ccccccc)\n\n## v1.0.0 (2025-01-01)\n";
+        let updated = prepend_sections(existing, &["## Unreleased\n\n- 新条目 (ddddddd)\n".to_string()]);
+
+        assert!(!updated.contains("旧条目"));
+        assert_eq!(updated.matches("## Unreleased").count(), 1);
+        assert!(updated.contains("## v1.0.0 (2025-01-01)"));
+    }
+
+    #[test]
+    fn reads_latest_recorded_version() {
+        let existing = "# Changelog\n\n## Unreleased\n\n## v1.2.0 (2025-02-01)\n\n## v1.10.0 (2025-03-01)\n";
+        assert_eq!(latest_recorded_version(existing), Version::parse("1.10.0"));
+        assert_eq!(latest_recorded_version("# Changelog\n\n## Unreleased\n"), None);
+    }
+
+    #[test]
+    fn release_then_incremental_moves_unreleased_into_version() {
+        let before_release = "# Changelog\n\n## Unreleased\n\n### 问题修复\n\n- 修复崩溃 (bbbbbbb)\n\n## v1.0.0 (2025-01-01)\n\n- 初始版本 (aaaaaaa)\n";
+        let released = "## v1.1.0 (2025-02-01)\n\n### 问题修复\n\n- 修复崩溃 (bbbbbbb)\n".to_string();
+
+        // 发布后没有新的提交：新增版本分节，去掉空的未发布分节
+        let updated = prepend_sections(before_release, std::slice::from_ref(&released));
+        assert_eq!(
+            updated,
+            "# Changelog\n\n## v1.1.0 (2025-02-01)\n\n### 问题修复\n\n- 修复崩溃 (bbbbbbb)\n\n## v1.0.0 (2025-01-01)\n\n- 初始版本 (aaaaaaa)\n"
+        );
+        assert_eq!(latest_recorded_version(&updated), Version::parse("1.1.0"));
+
+        // 发布后又有新的提交：未发布分节在最前，已发布的条目保留在版本分节中
+        let unreleased = "## Unreleased\n\n- 新功能 (ccccccc)\n".to_string();
+        let updated = prepend_sections(before_release, &[unreleased, released]);
+        assert!(updated.starts_with("# Changelog\n\n## Unreleased\n\n- 新功能 (ccccccc)\n\n## v1.1.0 (2025-02-01)\n"));
+        assert_eq!(updated.matches("修复崩溃").count(), 1);
+        assert!(updated.contains("## v1.0.0 (2025-01-01)"));
+    }
+
+    #[test]
+    fn prepend_sections_adds_missing_header() {
+        let updated = prepend_sections("", &["## Unreleased\n\n- 新条目 (ddddddd)\n".to_string()]);
+        assert_eq!(updated, "# Changelog\n\n## Unreleased\n\n- 新条目 (ddddddd)\n");
+    }
+}
@@ -0,0 +1,164 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::process::Command;
+
+/// 合作者名单的配置项前缀，例如 `og.coauthor.alice = Alice <alice@example.com>`
+const CO_AUTHOR_PREFIX: &str = "og.coauthor.";
+
+/// 读取 git 配置中的单值配置项
+///
+/// # 参数
+/// * `key` - 配置项名称 (例如: user.email)
+///
+/// # 返回值
+/// 返回配置值，未配置时返回None
+pub fn get(key: &str) -> Result<Option<String>> {
+    Ok(get_all(key)?.pop())
+}
+
+/// 读取 git 配置中的多值配置项
+///
+/// 通过 `git config --add og.scope parser` 可为同一配置项添加多个值。
+///
+/// # 参数
+/// * `key` - 配置项名称 (例如: og.scope)
+///
+/// # 返回值
+/// 返回按配置顺序排列的值列表，未配置时返回空列表
+pub fn get_all(key: &str) -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["config", "--get-all", key])
+        .output()
+        .context("执行'git config'失败")?;
+
+    // 配置项不存在时 git config 以状态码 1 退出
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+/// 读取名称匹配正则表达式的所有配置项
+///
+/// # 参数
+/// * `pattern` - 配置项名称的正则表达式 (例如: ^og\.coauthor\.)
+///
+/// # 返回值
+/// 返回 (配置项名称, 值) 列表，配置项名称为小写形式
+pub fn get_regexp(pattern: &str) -> Result<Vec<(String, String)>> {
+    let output = Command::new("git")
+        .args(["config", "--get-regexp", pattern])
+        .output()
+        .context("执行'git config'失败")?;
+
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(' ')?;
+            Some((key.to_string(), value.trim().to_string()))
+        })
+        .collect())
+}
+
+/// 写入当前仓库的 git 配置项
+///
+/// # 参数
+/// * `key` - 配置项名称
+/// * `value` - 配置值
+pub fn set(key: &str, value: &str) -> Result<()> {
+    println!("{} {}", ">".bright_cyan(), format!("git config {} \"{}\"", key, value).bright_yellow());
+
+    let status = Command::new("git")
+        .args(["config", key, value])
+        .status()
+        .context("执行'git config'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git config'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 读取合作者名单
+///
+/// # 返回值
+/// 返回 (简称, `Name <email>`) 列表
+pub fn co_authors() -> Result<Vec<(String, String)>> {
+    Ok(get_regexp(r"^og\.coauthor\.")?
+        .into_iter()
+        .filter_map(|(key, identity)| {
+            let handle = key.strip_prefix(CO_AUTHOR_PREFIX)?.to_string();
+            Some((handle, identity))
+        })
+        .collect())
+}
+
+/// 将合作者添加到当前仓库的名单中
+///
+/// # 参数
+/// * `handle` - 简称 (例如: alice)
+/// * `identity` - `Name <email>` 形式的身份
+pub fn add_co_author(handle: &str, identity: &str) -> Result<()> {
+    set(&format!("{}{}", CO_AUTHOR_PREFIX, handle), identity)
+}
+
+/// 将简称列表解析为合作者身份
+///
+/// # 参数
+/// * `handles` - 简称列表，也可以直接写 `Name <email>`
+///
+/// # 返回值
+/// 返回 `Name <email>` 形式的身份列表，存在未知简称时返回错误
+pub fn resolve_co_authors(handles: &[String]) -> Result<Vec<String>> {
+    let roster = co_authors()?;
+
+    handles
+        .iter()
+        .map(|handle| {
+            let handle = handle.trim();
+            if handle.contains('<') {
+                return Ok(handle.to_string());
+            }
+
+            roster
+                .iter()
+                .find(|(h, _)| h.eq_ignore_ascii_case(handle))
+                .map(|(_, identity)| identity.clone())
+                .with_context(|| {
+                    let known: Vec<&str> = roster.iter().map(|(h, _)| h.as_str()).collect();
+                    format!("未知的合作者简称 [{}]，已配置的简称: {}", handle, known.join(", "))
+                })
+        })
+        .collect()
+}
+
+/// 读取提交类型的正文模板
+///
+/// 模板通过 `git config --add og.template.fix "Root cause*"` 配置，每个值为一个字段，
+/// 以 `*` 结尾的字段为必填项；未配置时不使用模板。
+///
+/// # 参数
+/// * `commit_type` - 提交类型
+///
+/// # 返回值
+/// 返回 (字段标签, 是否必填) 列表，没有模板时返回空列表
+pub fn template(commit_type: &str) -> Result<Vec<(String, bool)>> {
+    Ok(get_all(&format!("og.template.{}", commit_type))?
+        .iter()
+        .map(|field| match field.strip_suffix('*') {
+            Some(label) => (label.trim().to_string(), true),
+            None => (field.trim().to_string(), false),
+        })
+        .filter(|(label, _)| !label.is_empty())
+        .collect())
+}
@@ -2,6 +2,18 @@ use anyhow::{Context, Result};
 use colored::Colorize;
 use std::process::Command;
 
+/// WIP 快照提交的标题前缀
+pub const WIP_PREFIX: &str = "[WIP] ";
+
+/// 清理分支时记录被删除分支末端提交的引用前缀
+pub const PRUNED_REF_PREFIX: &str = "refs/ogit/pruned/";
+
+/// 回退前备份HEAD的引用前缀
+pub const BACKUP_REF_PREFIX: &str = "refs/ogit/backup/";
+
+/// 回退前备份未提交变更快照的引用前缀
+pub const BACKUP_WORKTREE_REF_PREFIX: &str = "refs/ogit/backup-worktree/";
+
 /// 提交变更到Git仓库
 ///
 /// # 参数
@@ -90,6 +102,85 @@ pub fn reset(mode: &str, target: &str) -> Result<()> {
     Ok(())
 }
 
+/// 统计从指定提交可以访问到的提交数量
+///
+/// # 参数
+/// * `rev` - 提交引用
+///
+/// # 返回值
+/// 返回提交数量
+pub fn commit_count(rev: &str) -> Result<usize> {
+    let output = Command::new("git")
+        .args(["rev-list", "--count", rev])
+        .output()
+        .context("执行'git rev-list --count'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git rev-list --count'命令执行失败");
+    }
+
+    Ok(String::from_utf8(output.stdout)?.trim().parse()?)
+}
+
+/// 检查当前仓库是否为浅克隆
+///
+/// # 返回值
+/// 是浅克隆返回true，否则返回false
+pub fn is_shallow() -> Result<bool> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--is-shallow-repository"])
+        .output()
+        .context("执行'git rev-parse --is-shallow-repository'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git rev-parse'命令执行失败");
+    }
+
+    Ok(String::from_utf8(output.stdout)?.trim() == "true")
+}
+
+/// 检查提交是否为另一个提交的祖先 (或为同一提交)
+///
+/// # 参数
+/// * `ancestor` - 可能的祖先提交
+/// * `descendant` - 后代提交 (通常为HEAD)
+///
+/// # 返回值
+/// 是祖先返回true，否则返回false
+pub fn is_ancestor(ancestor: &str, descendant: &str) -> Result<bool> {
+    let status = Command::new("git")
+        .args(["merge-base", "--is-ancestor", ancestor, descendant])
+        .status()
+        .context("执行'git merge-base --is-ancestor'失败")?;
+
+    // 退出码 0 表示是祖先，1 表示不是，其余为错误
+    match status.code() {
+        Some(0) => Ok(true),
+        Some(1) => Ok(false),
+        _ => anyhow::bail!("'git merge-base --is-ancestor'命令执行失败"),
+    }
+}
+
+/// 删除当前分支引用，使其回到尚无提交的状态 (保留暂存区和工作区)
+///
+/// 用于软回退到根提交之前，此时 `HEAD~n` 不存在，无法使用 `git reset --soft`
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn unborn_head() -> Result<()> {
+    println!("{} {}", ">".bright_cyan(), "git update-ref -d HEAD".bright_yellow());
+    let status = Command::new("git")
+        .args(["update-ref", "-d", "HEAD"])
+        .status()
+        .context("执行'git update-ref'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git update-ref'命令执行失败");
+    }
+
+    Ok(())
+}
+
 /// 检查是否有修改需要提交
 ///
 /// # 返回值
@@ -157,3 +248,1645 @@ pub fn is_diverged(remote: &str) -> Result<bool> {
     // 如果merge-base是本地HEAD，说明本地落后于远程
     Ok(merge_base != remote_head)
 }
+
+/// 获取HEAD提交的完整提交消息
+///
+/// # 返回值
+/// 返回HEAD的提交消息
+pub fn head_message() -> Result<String> {
+    commit_message("HEAD").context("无法读取HEAD提交消息，仓库中可能还没有提交")
+}
+
+/// 获取指定提交的完整提交消息
+///
+/// # 参数
+/// * `rev` - 提交引用
+///
+/// # 返回值
+/// 返回提交消息
+pub fn commit_message(rev: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%B", rev])
+        .output()
+        .context("执行'git log'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("无法读取提交消息: {}", rev);
+    }
+
+    Ok(String::from_utf8(output.stdout)?.trim_end().to_string())
+}
+
+/// 修改最近一次提交 (git commit --amend)
+///
+/// # 参数
+/// * `commit_message` - 新的提交消息
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn commit_amend(commit_message: &str) -> Result<()> {
+    println!("{} {}", ">".bright_cyan(), "git add .".bright_yellow());
+    let status = Command::new("git")
+        .args(["add", "."])
+        .status()
+        .context("执行'git add .'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git add .'命令执行失败");
+    }
+
+    println!("{} {}", ">".bright_cyan(), format!("git commit --amend -m \"{}\"", commit_message).bright_yellow());
+    let status = Command::new("git")
+        .args(["commit", "--amend", "-m", commit_message])
+        .status()
+        .context("执行'git commit --amend'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git commit --amend'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 检查指定提交是否已经存在于远程分支上
+///
+/// # 参数
+/// * `rev` - 提交引用 (例如: HEAD, a1b2c3d)
+///
+/// # 返回值
+/// 如果任一远程跟踪分支包含该提交，返回true，否则返回false
+pub fn is_pushed(rev: &str) -> Result<bool> {
+    let output = Command::new("git")
+        .args(["branch", "-r", "--contains", rev])
+        .output()
+        .context("执行'git branch -r --contains'失败")?;
+
+    if !output.status.success() {
+        return Ok(false);
+    }
+
+    Ok(!String::from_utf8(output.stdout)?.trim().is_empty())
+}
+
+/// 提交摘要信息
+#[derive(Debug, Clone)]
+pub struct CommitInfo {
+    /// 完整哈希
+    pub hash: String,
+    /// 短哈希
+    pub short_hash: String,
+    /// 提交标题
+    pub subject: String,
+    /// 作者名称
+    pub author: String,
+    /// 相对提交日期 (例如: 2 days ago)
+    pub date: String,
+}
+
+/// 列出指定范围内的提交，按时间从新到旧排列
+///
+/// # 参数
+/// * `range` - 提交范围 (例如: HEAD, origin/main..HEAD)
+/// * `limit` - 最多返回的提交数量
+///
+/// # 返回值
+/// 返回提交摘要列表
+pub fn list_commits(range: &str, limit: usize) -> Result<Vec<CommitInfo>> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            &format!("-{}", limit),
+            COMMIT_FORMAT,
+            range,
+            "--",
+        ])
+        .output()
+        .context("执行'git log'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git log'命令执行失败");
+    }
+
+    Ok(parse_commit_lines(&String::from_utf8(output.stdout)?))
+}
+
+/// 沿第一父提交列出从指定提交开始的提交哈希
+///
+/// 列表中的下标即该提交相对起点的 `~n` 距离
+///
+/// # 参数
+/// * `rev` - 起始提交
+/// * `limit` - 最多返回的提交数量
+///
+/// # 返回值
+/// 返回完整哈希列表
+pub fn first_parent_hashes(rev: &str, limit: usize) -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["rev-list", "--first-parent", &format!("-{}", limit), rev, "--"])
+        .output()
+        .context("执行'git rev-list'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git rev-list'命令执行失败");
+    }
+
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .collect())
+}
+
+/// 日志格式：完整哈希、短哈希、标题、作者、相对日期，以 \x1f 分隔
+const COMMIT_FORMAT: &str = "--format=%H%x1f%h%x1f%s%x1f%an%x1f%ar";
+
+/// 解析以 COMMIT_FORMAT 格式输出的提交列表
+fn parse_commit_lines(text: &str) -> Vec<CommitInfo> {
+    text
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\x1f').collect();
+            if fields.len() < 5 {
+                return None;
+            }
+            Some(CommitInfo {
+                hash: fields[0].to_string(),
+                short_hash: fields[1].to_string(),
+                subject: fields[2].to_string(),
+                author: fields[3].to_string(),
+                date: fields[4].to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 获取当前分支的上游分支名称
+///
+/// # 返回值
+/// 返回上游分支 (例如: origin/main)，未设置上游时返回None
+pub fn upstream() -> Result<Option<String>> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"])
+        .output()
+        .context("获取上游分支失败")?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let name = String::from_utf8(output.stdout)?.trim().to_string();
+    Ok(if name.is_empty() { None } else { Some(name) })
+}
+
+/// 检查暂存区是否有变更
+///
+/// # 返回值
+/// 如果暂存区有变更，返回true，否则返回false
+pub fn has_staged_changes() -> Result<bool> {
+    let status = Command::new("git")
+        .args(["diff", "--cached", "--quiet"])
+        .status()
+        .context("执行'git diff --cached'失败")?;
+
+    Ok(!status.success())
+}
+
+/// 以暂存区的变更创建 fixup! 提交
+///
+/// # 参数
+/// * `target` - 要修正的目标提交
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn commit_fixup(target: &str) -> Result<()> {
+    println!("{} {}", ">".bright_cyan(), format!("git commit --fixup={}", target).bright_yellow());
+    let status = Command::new("git")
+        .args(["commit", &format!("--fixup={}", target)])
+        .status()
+        .context("执行'git commit --fixup'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git commit --fixup'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 以非交互方式执行 autosquash 变基，将 fixup!/squash! 提交合并到目标提交
+///
+/// # 参数
+/// * `base` - 变基的基准 (通常为上游分支)
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn autosquash(base: &str) -> Result<()> {
+    println!("{} {}", ">".bright_cyan(), format!("git rebase -i --autosquash {}", base).bright_yellow());
+    let status = Command::new("git")
+        .args(["rebase", "-i", "--autosquash", base])
+        // 使用空操作的编辑器接受自动生成的变基计划，避免打开交互式编辑器
+        .env("GIT_SEQUENCE_EDITOR", "true")
+        .status()
+        .context("执行'git rebase --autosquash'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git rebase --autosquash'命令执行失败，请解决冲突后执行 'git rebase --continue'，或执行 'git rebase --abort' 放弃");
+    }
+
+    Ok(())
+}
+
+/// 统计HEAD处连续的、尚未推送的 WIP 快照提交
+///
+/// # 返回值
+/// 返回从HEAD开始连续的 WIP 提交列表，按时间从新到旧排列
+pub fn head_wip_commits() -> Result<Vec<CommitInfo>> {
+    let range = match upstream()? {
+        Some(upstream) => format!("{}..HEAD", upstream),
+        None => "HEAD".to_string(),
+    };
+
+    let commits = list_commits(&range, 100)?
+        .into_iter()
+        .take_while(|c| c.subject.starts_with(WIP_PREFIX))
+        .collect();
+
+    Ok(commits)
+}
+
+/// 储藏条目信息
+#[derive(Debug, Clone)]
+pub struct StashEntry {
+    /// 储藏引用 (例如: stash@{0})
+    pub reference: String,
+    /// 储藏说明
+    pub message: String,
+    /// 相对创建日期
+    pub date: String,
+}
+
+/// 储藏当前工作区和暂存区的变更 (包含未跟踪文件)
+///
+/// # 参数
+/// * `message` - 储藏说明
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn stash_save(message: &str) -> Result<()> {
+    println!("{} {}", ">".bright_cyan(), format!("git stash push -u -m \"{}\"", message).bright_yellow());
+    let status = Command::new("git")
+        .args(["stash", "push", "-u", "-m", message])
+        .status()
+        .context("执行'git stash push'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git stash push'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 列出所有储藏，按时间从新到旧排列
+///
+/// # 返回值
+/// 返回储藏条目列表
+pub fn stash_list() -> Result<Vec<StashEntry>> {
+    let output = Command::new("git")
+        .args(["stash", "list", "--format=%gd%x1f%gs%x1f%cr"])
+        .output()
+        .context("执行'git stash list'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git stash list'命令执行失败");
+    }
+
+    let entries = String::from_utf8(output.stdout)?
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\x1f').collect();
+            if fields.len() < 3 {
+                return None;
+            }
+            Some(StashEntry {
+                reference: fields[0].to_string(),
+                message: fields[1].to_string(),
+                date: fields[2].to_string(),
+            })
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// 显示储藏的变更统计 (diffstat)
+///
+/// # 参数
+/// * `reference` - 储藏引用
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn stash_show(reference: &str) -> Result<()> {
+    println!("{} {}", ">".bright_cyan(), format!("git stash show --stat --include-untracked {}", reference).bright_yellow());
+    let status = Command::new("git")
+        .args(["stash", "show", "--stat", "--include-untracked", reference])
+        .status()
+        .context("执行'git stash show'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git stash show'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 应用储藏到工作区
+///
+/// # 参数
+/// * `reference` - 储藏引用
+/// * `pop` - 应用成功后是否删除该储藏
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn stash_apply(reference: &str, pop: bool) -> Result<()> {
+    let action = if pop { "pop" } else { "apply" };
+    println!("{} {}", ">".bright_cyan(), format!("git stash {} {}", action, reference).bright_yellow());
+    let status = Command::new("git")
+        .args(["stash", action, reference])
+        .status()
+        .with_context(|| format!("执行'git stash {}'失败", action))?;
+
+    if !status.success() {
+        anyhow::bail!("'git stash {}'命令执行失败，可能存在冲突，请手动解决", action);
+    }
+
+    Ok(())
+}
+
+/// 分支信息
+#[derive(Debug, Clone)]
+pub struct BranchInfo {
+    /// 分支名称 (远程分支包含远程名前缀，例如: origin/main)
+    pub name: String,
+    /// 是否为远程跟踪分支
+    pub is_remote: bool,
+    /// 是否为当前分支
+    pub is_current: bool,
+    /// 上游分支名称
+    pub upstream: Option<String>,
+    /// 上游分支是否已在远程被删除
+    pub upstream_gone: bool,
+    /// 最后一次提交的相对日期
+    pub date: String,
+    /// 最后一次提交的标题
+    pub subject: String,
+}
+
+/// 列出本地分支和远程跟踪分支
+///
+/// # 参数
+/// * `include_remote` - 是否包含远程跟踪分支
+///
+/// # 返回值
+/// 返回分支信息列表
+pub fn list_branches(include_remote: bool) -> Result<Vec<BranchInfo>> {
+    let mut refs = vec!["refs/heads"];
+    if include_remote {
+        refs.push("refs/remotes");
+    }
+
+    let output = Command::new("git")
+        .arg("for-each-ref")
+        .arg("--sort=-committerdate")
+        .arg("--format=%(refname)%1f%(refname:short)%1f%(HEAD)%1f%(upstream:short)%1f%(committerdate:relative)%1f%(subject)%1f%(upstream:track)")
+        .args(&refs)
+        .output()
+        .context("执行'git for-each-ref'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git for-each-ref'命令执行失败");
+    }
+
+    let branches = String::from_utf8(output.stdout)?
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\x1f').collect();
+            if fields.len() < 7 || fields[0].ends_with("/HEAD") {
+                return None;
+            }
+            Some(BranchInfo {
+                name: fields[1].to_string(),
+                is_remote: fields[0].starts_with("refs/remotes/"),
+                is_current: fields[2] == "*",
+                upstream: if fields[3].is_empty() { None } else { Some(fields[3].to_string()) },
+                upstream_gone: fields[6] == "[gone]",
+                date: fields[4].to_string(),
+                subject: fields[5].to_string(),
+            })
+        })
+        .collect();
+
+    Ok(branches)
+}
+
+/// 统计分支相对于另一引用领先和落后的提交数
+///
+/// # 参数
+/// * `branch` - 分支名称
+/// * `base` - 比较的基准 (通常为上游分支)
+///
+/// # 返回值
+/// 返回 (领先数, 落后数)
+pub fn ahead_behind(branch: &str, base: &str) -> Result<(usize, usize)> {
+    let output = Command::new("git")
+        .args(["rev-list", "--left-right", "--count", &format!("{}...{}", branch, base)])
+        .output()
+        .context("执行'git rev-list'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git rev-list'命令执行失败");
+    }
+
+    let text = String::from_utf8(output.stdout)?;
+    let mut counts = text.split_whitespace().map(|n| n.parse::<usize>().unwrap_or(0));
+    Ok((counts.next().unwrap_or(0), counts.next().unwrap_or(0)))
+}
+
+/// 检查分支名是否为合法的引用名
+///
+/// # 参数
+/// * `name` - 分支名称
+///
+/// # 返回值
+/// 合法返回true，否则返回false
+pub fn is_valid_branch_name(name: &str) -> Result<bool> {
+    let output = Command::new("git")
+        .args(["check-ref-format", "--branch", name])
+        .output()
+        .context("执行'git check-ref-format'失败")?;
+
+    Ok(output.status.success())
+}
+
+/// 创建并切换到新分支
+///
+/// # 参数
+/// * `name` - 新分支名称
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn create_branch(name: &str) -> Result<()> {
+    println!("{} {}", ">".bright_cyan(), format!("git switch -c {}", name).bright_yellow());
+    let status = Command::new("git")
+        .args(["switch", "-c", name])
+        .status()
+        .context("执行'git switch -c'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git switch -c'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 切换到指定分支，远程分支会创建同名的本地跟踪分支
+///
+/// # 参数
+/// * `branch` - 要切换的分支
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn switch_branch(branch: &BranchInfo) -> Result<()> {
+    let mut command = Command::new("git");
+    command.arg("switch");
+
+    if branch.is_remote {
+        command.args(["--track", &branch.name]);
+        println!("{} {}", ">".bright_cyan(), format!("git switch --track {}", branch.name).bright_yellow());
+    } else {
+        command.arg(&branch.name);
+        println!("{} {}", ">".bright_cyan(), format!("git switch {}", branch.name).bright_yellow());
+    }
+
+    let status = command.status().context("执行'git switch'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git switch'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 获取提交引用对应的完整哈希
+///
+/// # 参数
+/// * `rev` - 提交引用 (例如: HEAD, main, a1b2c3d)
+///
+/// # 返回值
+/// 返回完整哈希
+pub fn rev_parse(rev: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", rev)])
+        .output()
+        .context("执行'git rev-parse'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("无法解析提交引用: {}", rev);
+    }
+
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+/// 从远程仓库获取更新
+///
+/// # 参数
+/// * `remote` - 远程仓库名称
+/// * `prune` - 是否清理远程已删除分支的跟踪引用
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn fetch(remote: &str, prune: bool) -> Result<()> {
+    let mut command = Command::new("git");
+    command.args(["fetch", remote]);
+
+    if prune {
+        command.arg("--prune");
+        println!("{} {}", ">".bright_cyan(), format!("git fetch {} --prune", remote).bright_yellow());
+    } else {
+        println!("{} {}", ">".bright_cyan(), format!("git fetch {}", remote).bright_yellow());
+    }
+
+    let status = command.status().context("执行'git fetch'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git fetch'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 获取默认分支名称
+///
+/// 优先读取远程仓库的 HEAD 指向，其次查找本地的 main 或 master 分支。
+///
+/// # 参数
+/// * `remote` - 远程仓库名称
+///
+/// # 返回值
+/// 返回默认分支名称 (例如: origin/main 或 main)
+pub fn default_branch(remote: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["symbolic-ref", "--short", &format!("refs/remotes/{}/HEAD", remote)])
+        .output()
+        .context("执行'git symbolic-ref'失败")?;
+
+    if output.status.success() {
+        let name = String::from_utf8(output.stdout)?.trim().to_string();
+        if !name.is_empty() {
+            return Ok(name);
+        }
+    }
+
+    for candidate in ["main", "master"] {
+        if rev_parse(candidate).is_ok() {
+            return Ok(candidate.to_string());
+        }
+    }
+
+    anyhow::bail!("无法确定默认分支，请确认远程仓库 [{}] 已设置 HEAD 或存在 main/master 分支", remote)
+}
+
+/// 列出已完全合并到指定分支的本地分支
+///
+/// # 参数
+/// * `base` - 合并目标分支
+///
+/// # 返回值
+/// 返回本地分支名称列表
+pub fn merged_branches(base: &str) -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["branch", "--merged", base, "--format=%(refname:short)"])
+        .output()
+        .context("执行'git branch --merged'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git branch --merged'命令执行失败");
+    }
+
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+/// 强制删除本地分支
+///
+/// # 参数
+/// * `name` - 分支名称
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn delete_branch(name: &str) -> Result<()> {
+    println!("{} {}", ">".bright_cyan(), format!("git branch -D {}", name).bright_yellow());
+    let status = Command::new("git")
+        .args(["branch", "-D", name])
+        .status()
+        .context("执行'git branch -D'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git branch -D'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 删除远程分支
+///
+/// # 参数
+/// * `remote` - 远程仓库名称
+/// * `name` - 远程仓库中的分支名称
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn delete_remote_branch(remote: &str, name: &str) -> Result<()> {
+    println!("{} {}", ">".bright_cyan(), format!("git push {} --delete {}", remote, name).bright_yellow());
+    let status = Command::new("git")
+        .args(["push", remote, "--delete", name])
+        .status()
+        .context("执行'git push --delete'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git push --delete'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 在指定提交处创建分支 (不切换)
+///
+/// # 参数
+/// * `name` - 分支名称
+/// * `start_point` - 分支起点
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn create_branch_at(name: &str, start_point: &str) -> Result<()> {
+    println!("{} {}", ">".bright_cyan(), format!("git branch {} {}", name, start_point).bright_yellow());
+    let status = Command::new("git")
+        .args(["branch", name, start_point])
+        .status()
+        .context("执行'git branch'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git branch'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 引用信息
+#[derive(Debug, Clone)]
+pub struct RefInfo {
+    /// 完整引用名称 (例如: refs/ogit/pruned/20240101-120000/feat/login)
+    pub refname: String,
+    /// 去掉前缀后的名称 (例如: 20240101-120000/feat/login)
+    pub name: String,
+    /// 指向的完整哈希
+    pub hash: String,
+    /// 指向提交的标题
+    pub subject: String,
+    /// 指向提交的日期
+    pub date: String,
+}
+
+/// 创建或更新引用
+///
+/// # 参数
+/// * `refname` - 完整引用名称
+/// * `target` - 引用指向的提交
+/// * `reason` - 写入reflog的说明
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn update_ref(refname: &str, target: &str, reason: &str) -> Result<()> {
+    let status = Command::new("git")
+        .args(["update-ref", "-m", reason, refname, target])
+        .status()
+        .context("执行'git update-ref'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git update-ref'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 删除引用
+///
+/// # 参数
+/// * `refname` - 完整引用名称
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn delete_ref(refname: &str) -> Result<()> {
+    let status = Command::new("git")
+        .args(["update-ref", "-d", refname])
+        .status()
+        .context("执行'git update-ref -d'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git update-ref -d'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 列出指定前缀下的引用，按提交时间从新到旧排列
+///
+/// # 参数
+/// * `prefix` - 引用前缀 (例如: refs/ogit/pruned/)
+///
+/// # 返回值
+/// 返回引用信息列表
+pub fn list_refs(prefix: &str) -> Result<Vec<RefInfo>> {
+    let output = Command::new("git")
+        .args([
+            "for-each-ref",
+            "--sort=-committerdate",
+            "--format=%(refname)%1f%(objectname)%1f%(subject)%1f%(committerdate:format:%Y/%m/%d %H:%M)",
+            prefix,
+        ])
+        .output()
+        .context("执行'git for-each-ref'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git for-each-ref'命令执行失败");
+    }
+
+    let refs = String::from_utf8(output.stdout)?
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\x1f').collect();
+            if fields.len() < 4 {
+                return None;
+            }
+            Some(RefInfo {
+                refname: fields[0].to_string(),
+                name: fields[0].strip_prefix(prefix).unwrap_or(fields[0]).to_string(),
+                hash: fields[1].to_string(),
+                subject: fields[2].to_string(),
+                date: fields[3].to_string(),
+            })
+        })
+        .collect();
+
+    Ok(refs)
+}
+
+/// 获取当前分支名称
+///
+/// # 返回值
+/// 返回当前分支名称，处于分离HEAD状态时返回 "HEAD"
+pub fn current_branch() -> Result<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .output()
+        .context("获取当前分支名称失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("无法获取当前分支名称");
+    }
+
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+/// 变基当前分支到指定的基准
+///
+/// # 参数
+/// * `base` - 变基的基准 (通常为上游分支)
+/// * `autostash` - 是否在变基前自动储藏未提交的变更，并在变基结束后恢复
+///
+/// # 返回值
+/// 变基完成返回true，因冲突而暂停返回false
+pub fn rebase(base: &str, autostash: bool) -> Result<bool> {
+    let mut command = Command::new("git");
+    command.arg("rebase");
+
+    if autostash {
+        command.arg("--autostash");
+        println!("{} {}", ">".bright_cyan(), format!("git rebase --autostash {}", base).bright_yellow());
+    } else {
+        println!("{} {}", ">".bright_cyan(), format!("git rebase {}", base).bright_yellow());
+    }
+
+    let status = command.arg(base).status().context("执行'git rebase'失败")?;
+
+    if status.success() {
+        return Ok(true);
+    }
+
+    if operation_in_progress("rebase")? {
+        return Ok(false);
+    }
+
+    anyhow::bail!("'git rebase'命令执行失败")
+}
+
+/// 获取进行中操作对应的特殊引用名称
+fn operation_head(operation: &str) -> Result<&'static str> {
+    match operation {
+        "rebase" => Ok("REBASE_HEAD"),
+        "revert" => Ok("REVERT_HEAD"),
+        "cherry-pick" => Ok("CHERRY_PICK_HEAD"),
+        "merge" => Ok("MERGE_HEAD"),
+        _ => anyhow::bail!("不支持的操作: {}", operation),
+    }
+}
+
+/// 检查指定操作 (rebase, revert, cherry-pick, merge) 是否因冲突暂停
+///
+/// # 参数
+/// * `operation` - 操作名称
+///
+/// # 返回值
+/// 如果操作正在进行中，返回true，否则返回false
+pub fn operation_in_progress(operation: &str) -> Result<bool> {
+    let status = Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", operation_head(operation)?])
+        .stdout(std::process::Stdio::null())
+        .status()
+        .context("执行'git rev-parse'失败")?;
+
+    Ok(status.success())
+}
+
+/// 列出存在冲突的文件
+///
+/// # 返回值
+/// 返回冲突文件路径列表
+pub fn conflicted_files() -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["diff", "--name-only", "--diff-filter=U"])
+        .output()
+        .context("执行'git diff --diff-filter=U'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git diff'命令执行失败");
+    }
+
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(|line| line.to_string())
+        .collect())
+}
+
+/// 继续因冲突暂停的操作
+///
+/// # 参数
+/// * `operation` - 操作名称 (rebase, revert, cherry-pick)
+///
+/// # 返回值
+/// 操作完成返回true，再次因冲突暂停返回false
+pub fn continue_operation(operation: &str) -> Result<bool> {
+    println!("{} {}", ">".bright_cyan(), format!("git {} --continue", operation).bright_yellow());
+    let status = Command::new("git")
+        .args([operation, "--continue"])
+        // 沿用自动生成的提交消息，避免打开编辑器
+        .env("GIT_EDITOR", "true")
+        .status()
+        .with_context(|| format!("执行'git {} --continue'失败", operation))?;
+
+    if status.success() {
+        return Ok(true);
+    }
+
+    if operation_in_progress(operation)? {
+        return Ok(false);
+    }
+
+    anyhow::bail!("'git {} --continue'命令执行失败", operation)
+}
+
+/// 放弃因冲突暂停的操作，恢复到操作前的状态
+///
+/// 以 --no-commit 方式执行的 cherry-pick 冲突时没有可以 --abort 的操作状态，
+/// 此时使用 `git reset --merge` 丢弃未合并的暂存区和工作区变更。
+///
+/// # 参数
+/// * `operation` - 操作名称 (rebase, revert, cherry-pick)
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn abort_operation(operation: &str) -> Result<()> {
+    if !operation_in_progress(operation)? {
+        return reset("merge", "HEAD");
+    }
+
+    println!("{} {}", ">".bright_cyan(), format!("git {} --abort", operation).bright_yellow());
+    let status = Command::new("git")
+        .args([operation, "--abort"])
+        .status()
+        .with_context(|| format!("执行'git {} --abort'失败", operation))?;
+
+    if !status.success() {
+        anyhow::bail!("'git {} --abort'命令执行失败", operation);
+    }
+
+    Ok(())
+}
+
+/// 获取仓库的 .git 目录路径
+///
+/// # 返回值
+/// 返回 .git 目录的路径
+pub fn git_dir() -> Result<std::path::PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-dir"])
+        .output()
+        .context("执行'git rev-parse --git-dir'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("当前目录不是Git仓库");
+    }
+
+    Ok(std::path::PathBuf::from(String::from_utf8(output.stdout)?.trim()))
+}
+
+/// 为工作区和暂存区的变更创建储藏提交，但不修改工作区 (git stash create)
+///
+/// # 参数
+/// * `message` - 快照说明
+///
+/// # 返回值
+/// 返回快照提交的哈希，没有未提交的变更时返回None
+pub fn stash_create(message: &str) -> Result<Option<String>> {
+    let output = Command::new("git")
+        .args(["stash", "create", message])
+        .output()
+        .context("执行'git stash create'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git stash create'命令执行失败");
+    }
+
+    let hash = String::from_utf8(output.stdout)?.trim().to_string();
+    Ok(if hash.is_empty() { None } else { Some(hash) })
+}
+
+/// 回退前创建安全备份
+///
+/// 将当前HEAD保存到 `refs/ogit/backup/<时间戳>`，hard 模式下还会将未提交的变更
+/// 快照保存到 `refs/ogit/backup-worktree/<时间戳>`。同一秒内已有备份时，
+/// 名称追加 `-2`、`-3` 等序号，避免覆盖之前的备份。
+///
+/// # 参数
+/// * `mode` - 回退模式
+/// * `target` - 回退目标
+///
+/// # 返回值
+/// 返回备份名称 (时间戳及可能的序号)
+pub fn create_backup(mode: &str, target: &str) -> Result<String> {
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let mut name = timestamp.clone();
+    let mut counter = 1;
+    while rev_parse(&format!("{}{}", BACKUP_REF_PREFIX, name)).is_ok() {
+        counter += 1;
+        name = format!("{}-{}", timestamp, counter);
+    }
+    let reason = format!("og reset --{} {}", mode, target);
+
+    update_ref(&format!("{}{}", BACKUP_REF_PREFIX, name), "HEAD", &reason)?;
+
+    if mode == "hard" {
+        if let Some(snapshot) = stash_create(&format!("og reset 前的未提交变更 ({})", name))? {
+            update_ref(&format!("{}{}", BACKUP_WORKTREE_REF_PREFIX, name), &snapshot, &reason)?;
+        }
+    }
+
+    Ok(name)
+}
+
+/// 获取当前分支上尚未推送到任何远程分支的提交
+///
+/// # 返回值
+/// 返回未推送提交的完整哈希集合
+pub fn unpushed_commits() -> Result<std::collections::HashSet<String>> {
+    let output = Command::new("git")
+        .args(["rev-list", "HEAD", "--not", "--remotes"])
+        .output()
+        .context("执行'git rev-list'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git rev-list'命令执行失败");
+    }
+
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .collect())
+}
+
+/// 文件变更统计
+#[derive(Debug, Clone)]
+pub struct FileChange {
+    /// 文件路径
+    pub path: String,
+    /// 新增行数 (二进制文件为None)
+    pub added: Option<usize>,
+    /// 删除行数 (二进制文件为None)
+    pub deleted: Option<usize>,
+}
+
+/// 统计工作区和暂存区相对于指定提交的变更
+///
+/// # 参数
+/// * `rev` - 比较的基准提交
+///
+/// # 返回值
+/// 返回每个变更文件的行数统计
+pub fn diff_numstat(rev: &str) -> Result<Vec<FileChange>> {
+    let output = Command::new("git")
+        .args(["diff", "--numstat", rev])
+        .output()
+        .context("执行'git diff --numstat'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git diff --numstat'命令执行失败");
+    }
+
+    Ok(parse_numstat(&String::from_utf8(output.stdout)?))
+}
+
+/// 解析 --numstat 格式的输出
+fn parse_numstat(text: &str) -> Vec<FileChange> {
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let added = fields.next()?;
+            let deleted = fields.next()?;
+            let path = fields.next()?;
+            Some(FileChange {
+                path: path.to_string(),
+                added: added.parse().ok(),
+                deleted: deleted.parse().ok(),
+            })
+        })
+        .collect()
+}
+
+/// 列出未被忽略的未跟踪文件
+///
+/// # 返回值
+/// 返回未跟踪文件路径列表
+pub fn untracked_files() -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["ls-files", "--others", "--exclude-standard"])
+        .output()
+        .context("执行'git ls-files'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git ls-files'命令执行失败");
+    }
+
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(|line| line.to_string())
+        .collect())
+}
+
+/// 检查指定提交中是否存在某个文件
+///
+/// # 参数
+/// * `rev` - 提交引用
+/// * `path` - 文件路径
+///
+/// # 返回值
+/// 如果文件存在，返回true，否则返回false
+pub fn path_exists_in(rev: &str, path: &str) -> Result<bool> {
+    let status = Command::new("git")
+        .args(["cat-file", "-e", &format!("{}:{}", rev, path)])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .context("执行'git cat-file'失败")?;
+
+    Ok(status.success())
+}
+
+/// 将指定文件恢复为某个提交中的版本，同时更新暂存区 (git checkout <rev> -- <paths>)
+///
+/// # 参数
+/// * `rev` - 提交引用
+/// * `paths` - 文件路径列表
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn checkout_paths(rev: &str, paths: &[&str]) -> Result<()> {
+    println!("{} {}", ">".bright_cyan(), format!("git checkout {} -- {}", rev, paths.join(" ")).bright_yellow());
+    let status = Command::new("git")
+        .args(["checkout", rev, "--"])
+        .args(paths)
+        .status()
+        .context("执行'git checkout'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git checkout'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 检查提交是否为合并提交 (有多个父提交)
+///
+/// # 参数
+/// * `commit` - 提交引用
+///
+/// # 返回值
+/// 是合并提交返回true，否则返回false
+pub fn is_merge_commit(commit: &str) -> Result<bool> {
+    let output = Command::new("git")
+        .args(["rev-list", "--parents", "-n", "1", commit])
+        .output()
+        .context("执行'git rev-list --parents'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git rev-list'命令执行失败");
+    }
+
+    Ok(String::from_utf8(output.stdout)?.split_whitespace().count() > 2)
+}
+
+/// 撤销指定提交的变更并写入暂存区，不自动提交 (git revert --no-commit)
+///
+/// 合并提交以第一父提交为主线撤销 (`-m 1`)，即撤销合并进来的整个分支。
+///
+/// # 参数
+/// * `commit` - 要撤销的提交
+/// * `merge` - 是否为合并提交
+///
+/// # 返回值
+/// 变更已写入暂存区返回true，因冲突而暂停返回false
+pub fn revert_no_commit(commit: &str, merge: bool) -> Result<bool> {
+    let mut args = vec!["revert", "--no-commit"];
+    if merge {
+        args.extend(["-m", "1"]);
+    }
+    args.push(commit);
+
+    println!("{} {}", ">".bright_cyan(), format!("git {}", args.join(" ")).bright_yellow());
+    let status = Command::new("git")
+        .args(&args)
+        .status()
+        .context("执行'git revert'失败")?;
+
+    if status.success() {
+        return Ok(true);
+    }
+
+    if operation_in_progress("revert")? {
+        return Ok(false);
+    }
+
+    anyhow::bail!("'git revert'命令执行失败")
+}
+
+/// 查找不在任何分支上的提交，例如 hard 回退或变基后丢失的提交
+///
+/// 候选提交来自 HEAD 的 reflog 和悬空提交 (git fsck)，排除仍可从本地或远程分支访问到的提交，
+/// 以及储藏自动生成的提交。
+///
+/// # 参数
+/// * `limit` - 最多返回的提交数量
+///
+/// # 返回值
+/// 返回丢失的提交列表，按时间从新到旧排列
+pub fn lost_commits(limit: usize) -> Result<Vec<CommitInfo>> {
+    let mut candidates: Vec<String> = Vec::new();
+
+    let reflog = Command::new("git")
+        .args(["reflog", "--format=%H"])
+        .output()
+        .context("执行'git reflog'失败")?;
+    if reflog.status.success() {
+        candidates.extend(String::from_utf8(reflog.stdout)?.lines().map(|l| l.trim().to_string()));
+    }
+
+    let fsck = Command::new("git")
+        .args(["fsck", "--no-reflogs", "--dangling", "--no-progress"])
+        .output()
+        .context("执行'git fsck'失败")?;
+    candidates.extend(
+        String::from_utf8(fsck.stdout)?
+            .lines()
+            .filter_map(|l| l.strip_prefix("dangling commit "))
+            .map(|l| l.trim().to_string()),
+    );
+
+    candidates.sort();
+    candidates.dedup();
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // 只保留无法从任何分支访问到的候选提交
+    let output = Command::new("git")
+        .args(["rev-list", "--no-walk=unsorted", "--stdin", "--not", "--branches", "--remotes"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            use std::io::Write;
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(candidates.join("\n").as_bytes())?;
+            }
+            child.wait_with_output()
+        })
+        .context("执行'git rev-list'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git rev-list'命令执行失败");
+    }
+
+    let mut commits = Vec::new();
+    for hash in String::from_utf8(output.stdout)?.lines() {
+        if let Some(commit) = list_commits(hash.trim(), 1)?.pop() {
+            let is_stash = ["WIP on ", "index on ", "untracked files on ", "On "]
+                .iter()
+                .any(|prefix| commit.subject.starts_with(prefix));
+            if !is_stash {
+                commits.push(commit);
+            }
+        }
+    }
+
+    commits.sort_by_key(|c| std::cmp::Reverse(commit_timestamp(&c.hash).unwrap_or(0)));
+    commits.truncate(limit);
+
+    Ok(commits)
+}
+
+/// 获取提交的时间戳 (秒)
+///
+/// # 参数
+/// * `rev` - 提交引用
+///
+/// # 返回值
+/// 返回提交时间的Unix时间戳
+pub fn commit_timestamp(rev: &str) -> Result<i64> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%ct", rev])
+        .output()
+        .context("执行'git log'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git log'命令执行失败");
+    }
+
+    Ok(String::from_utf8(output.stdout)?.trim().parse()?)
+}
+
+/// 获取提交的变更统计摘要 (例如: 3 files changed, 10 insertions(+))
+///
+/// # 参数
+/// * `rev` - 提交引用
+///
+/// # 返回值
+/// 返回变更统计摘要
+pub fn commit_shortstat(rev: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["show", "--shortstat", "--format=", rev])
+        .output()
+        .context("执行'git show --shortstat'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git show'命令执行失败");
+    }
+
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+/// 将指定提交应用到当前分支 (git cherry-pick)
+///
+/// # 参数
+/// * `commit` - 要应用的提交
+/// * `no_commit` - 是否只将变更写入暂存区而不自动提交
+///
+/// # 返回值
+/// 应用完成返回true，因冲突而暂停返回false
+pub fn cherry_pick(commit: &str, no_commit: bool) -> Result<bool> {
+    let mut command = Command::new("git");
+    command.arg("cherry-pick");
+
+    if no_commit {
+        command.arg("--no-commit");
+        println!("{} {}", ">".bright_cyan(), format!("git cherry-pick --no-commit {}", commit).bright_yellow());
+    } else {
+        println!("{} {}", ">".bright_cyan(), format!("git cherry-pick {}", commit).bright_yellow());
+    }
+
+    let status = command.arg(commit).status().context("执行'git cherry-pick'失败")?;
+
+    if status.success() {
+        return Ok(true);
+    }
+
+    // --no-commit 模式下发生冲突时 git 不会写入 CHERRY_PICK_HEAD，只能通过未合并的暂存区判断
+    if operation_in_progress("cherry-pick")? || (no_commit && !conflicted_files()?.is_empty()) {
+        return Ok(false);
+    }
+
+    anyhow::bail!("'git cherry-pick'命令执行失败")
+}
+
+/// 列出另一分支上尚未应用到当前分支的提交，按时间从旧到新排列
+///
+/// 内容相同的提交 (例如已被 cherry-pick 过的提交) 会被排除。
+///
+/// # 参数
+/// * `branch` - 来源分支
+///
+/// # 返回值
+/// 返回提交摘要列表
+pub fn commits_not_on_head(branch: &str) -> Result<Vec<CommitInfo>> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--reverse",
+            "--no-merges",
+            "--cherry-pick",
+            "--right-only",
+            COMMIT_FORMAT,
+            &format!("HEAD...{}", branch),
+            "--",
+        ])
+        .output()
+        .context("执行'git log'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git log'命令执行失败");
+    }
+
+    Ok(parse_commit_lines(&String::from_utf8(output.stdout)?))
+}
+
+/// 获取当前分支上指定时间 (含) 之前的最后一个提交
+///
+/// # 参数
+/// * `before` - 时间 (例如: 2025-03-30 23:59:59)
+///
+/// # 返回值
+/// 返回找到的提交，不存在时返回None
+pub fn last_commit_before(before: &str) -> Result<Option<CommitInfo>> {
+    let output = Command::new("git")
+        .args(["rev-list", "-1", "--first-parent", &format!("--before={}", before), "HEAD"])
+        .output()
+        .context("执行'git rev-list --before'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git rev-list'命令执行失败");
+    }
+
+    let hash = String::from_utf8(output.stdout)?.trim().to_string();
+    if hash.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(list_commits(&hash, 1)?.pop())
+}
+
+/// 在当前分支的历史中按标题查找最近的提交，gitmoji 的短代码和表情形式视为相同
+///
+/// # 参数
+/// * `subject` - 提交标题
+///
+/// # 返回值
+/// 返回找到的提交，不存在时返回None
+pub fn find_commit_by_subject(subject: &str) -> Result<Option<CommitInfo>> {
+    Ok(list_commits("HEAD", 10000)?
+        .into_iter()
+        .find(|c| crate::log_manager::log_title(&c.subject) == crate::log_manager::log_title(subject)))
+}
+
+/// 获取当前分支上最近的 `vX.Y.Z` 版本标签
+///
+/// # 返回值
+/// 返回标签名称，没有版本标签时返回None
+pub fn latest_version_tag() -> Result<Option<String>> {
+    let output = Command::new("git")
+        .args(["describe", "--tags", "--abbrev=0", "--match", "v[0-9]*.[0-9]*.[0-9]*"])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .context("执行'git describe'失败")?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let tag = String::from_utf8(output.stdout)?.trim().to_string();
+    Ok(if tag.is_empty() { None } else { Some(tag) })
+}
+
+/// 列出指定范围内提交的完整提交消息，按时间从新到旧排列
+///
+/// # 参数
+/// * `range` - 提交范围 (例如: v1.0.0..HEAD)
+///
+/// # 返回值
+/// 返回 (完整哈希, 提交消息) 列表
+pub fn commit_messages(range: &str) -> Result<Vec<(String, String)>> {
+    let output = Command::new("git")
+        .args(["log", "--format=%H%x1f%B%x1e", range, "--"])
+        .output()
+        .context("执行'git log'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git log'命令执行失败");
+    }
+
+    Ok(String::from_utf8(output.stdout)?
+        .split('\x1e')
+        .filter_map(|record| {
+            let (hash, message) = record.trim_start_matches('\n').split_once('\x1f')?;
+            Some((hash.to_string(), message.trim_end().to_string()))
+        })
+        .collect())
+}
+
+/// 创建附注标签
+///
+/// # 参数
+/// * `name` - 标签名称
+/// * `message` - 标签说明
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn create_tag(name: &str, message: &str) -> Result<()> {
+    println!("{} {}", ">".bright_cyan(), format!("git tag -a {} -m \"{}\"", name, message).bright_yellow());
+    let status = Command::new("git")
+        .args(["tag", "-a", name, "-m", message])
+        .status()
+        .context("执行'git tag'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("'git tag'命令执行失败");
+    }
+
+    Ok(())
+}
+
+/// 推送标签到远程仓库
+///
+/// # 参数
+/// * `remote` - 远程仓库名称
+/// * `tag` - 标签名称
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn push_tag(remote: &str, tag: &str) -> Result<()> {
+    println!("{} {}", ">".bright_cyan(), format!("git push {} {}", remote, tag).bright_yellow());
+    let status = Command::new("git")
+        .args(["push", remote, tag])
+        .status()
+        .context("执行'git push'失败")?;
+
+    if !status.success() {
+        anyhow::bail!("推送标签失败");
+    }
+
+    Ok(())
+}
+
+/// 列出当前分支历史中的 `vX.Y.Z` 版本标签，按版本从新到旧排列
+///
+/// # 返回值
+/// 返回标签名称列表
+pub fn version_tags() -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["tag", "--merged", "HEAD", "--list", "v[0-9]*", "--sort=-v:refname"])
+        .output()
+        .context("执行'git tag'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git tag'命令执行失败");
+    }
+
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|tag| crate::release::Version::parse(tag).is_some())
+        .collect())
+}
+
+/// 获取提交或标签所指提交的日期
+///
+/// # 参数
+/// * `rev` - 提交引用或标签
+///
+/// # 返回值
+/// 返回 YYYY-MM-DD 格式的日期
+pub fn commit_date(rev: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%cd", "--date=format:%Y-%m-%d", rev])
+        .output()
+        .context("执行'git log'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git log'命令执行失败");
+    }
+
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+/// 获取远程仓库的网页地址
+///
+/// 支持 `git@host:user/repo.git`、`ssh://git@host/user/repo.git` 和 `https://host/user/repo.git` 形式。
+///
+/// # 参数
+/// * `remote` - 远程仓库名称
+///
+/// # 返回值
+/// 返回网页地址 (例如: https://github.com/iisyw/OGit)，无法识别时返回None
+pub fn remote_web_url(remote: &str) -> Result<Option<String>> {
+    let output = Command::new("git")
+        .args(["remote", "get-url", remote])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .context("执行'git remote get-url'失败")?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let url = String::from_utf8(output.stdout)?.trim().to_string();
+    let url = url.strip_suffix(".git").unwrap_or(&url);
+
+    let web_url = if let Some(rest) = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")) {
+        // 去掉地址中可能包含的用户名和令牌
+        let rest = rest.rsplit_once('@').map_or(rest, |(_, host)| host);
+        Some(format!("https://{}", rest))
+    } else if let Some(rest) = url.strip_prefix("ssh://") {
+        let rest = rest.split_once('@').map_or(rest, |(_, host)| host);
+        // 去掉ssh地址中的端口号
+        let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let host = host.split(':').next().unwrap_or(host);
+        Some(format!("https://{}/{}", host, path))
+    } else if let Some((user_host, path)) = url.split_once(':') {
+        let host = user_host.split_once('@').map_or(user_host, |(_, host)| host);
+        Some(format!("https://{}/{}", host, path))
+    } else {
+        None
+    };
+
+    Ok(web_url)
+}
+
+/// 列出工作区中有变更的文件，包括已暂存、未暂存和未跟踪的文件
+///
+/// # 返回值
+/// 返回文件路径列表，重命名的文件返回新路径
+pub fn changed_files() -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["-c", "core.quotePath=false", "status", "--porcelain", "--untracked-files=all"])
+        .output()
+        .context("执行'git status'失败")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'git status'命令执行失败");
+    }
+
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .filter_map(|line| line.get(3..))
+        .map(|path| {
+            let path = path.rsplit_once(" -> ").map_or(path, |(_, new)| new);
+            path.trim_matches('"').to_string()
+        })
+        .collect())
+}
+
+/// 列出提交历史中的所有作者，按提交数从多到少排列
+///
+/// # 返回值
+/// 返回 (姓名, 邮箱, 提交数) 列表
+pub fn log_authors() -> Result<Vec<(String, String, usize)>> {
+    let output = Command::new("git")
+        .args(["log", "--format=%aN%x1f%aE"])
+        .output()
+        .context("执行'git log'失败")?;
+
+    // 空仓库没有提交历史
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    let mut authors: Vec<(String, String, usize)> = Vec::new();
+    for line in String::from_utf8(output.stdout)?.lines() {
+        let Some((name, email)) = line.split_once('\x1f') else {
+            continue;
+        };
+        match authors.iter_mut().find(|(_, e, _)| e.eq_ignore_ascii_case(email)) {
+            Some((_, _, count)) => *count += 1,
+            None => authors.push((name.to_string(), email.to_string(), 1)),
+        }
+    }
+
+    authors.sort_by_key(|(_, _, count)| std::cmp::Reverse(*count));
+    Ok(authors)
+}
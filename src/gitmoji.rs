@@ -0,0 +1,137 @@
+use anyhow::Result;
+
+use crate::config;
+
+/// gitmoji 定义
+#[derive(Debug, PartialEq, Eq)]
+pub struct Gitmoji {
+    /// 表情符号 (例如: ✨)
+    pub emoji: &'static str,
+    /// 短代码 (例如: :sparkles:)
+    pub code: &'static str,
+    /// 对应的 Conventional Commits 类型，用于 CHANGELOG 和版本计算
+    pub commit_type: &'static str,
+    /// 说明
+    pub description: &'static str,
+}
+
+/// 常用 gitmoji 列表，每种提交类型的第一项为该类型的默认表情
+pub const GITMOJIS: &[Gitmoji] = &[
+    Gitmoji { emoji: "✨", code: ":sparkles:", commit_type: "feat", description: "引入新功能" },
+    Gitmoji { emoji: "🐛", code: ":bug:", commit_type: "fix", description: "修复 Bug" },
+    Gitmoji { emoji: "🚑️", code: ":ambulance:", commit_type: "fix", description: "紧急修复" },
+    Gitmoji { emoji: "🔒️", code: ":lock:", commit_type: "fix", description: "修复安全问题" },
+    Gitmoji { emoji: "📝", code: ":memo:", commit_type: "docs", description: "添加或更新文档" },
+    Gitmoji { emoji: "🎨", code: ":art:", commit_type: "style", description: "改进代码结构或格式" },
+    Gitmoji { emoji: "🚨", code: ":rotating_light:", commit_type: "style", description: "修复编译器或 linter 警告" },
+    Gitmoji { emoji: "♻️", code: ":recycle:", commit_type: "refactor", description: "重构代码" },
+    Gitmoji { emoji: "🔥", code: ":fire:", commit_type: "refactor", description: "删除代码或文件" },
+    Gitmoji { emoji: "⚡️", code: ":zap:", commit_type: "perf", description: "提升性能" },
+    Gitmoji { emoji: "✅", code: ":white_check_mark:", commit_type: "test", description: "添加、更新或通过测试" },
+    Gitmoji { emoji: "📦️", code: ":package:", commit_type: "build", description: "添加或更新编译产物和依赖包" },
+    Gitmoji { emoji: "➕", code: ":heavy_plus_sign:", commit_type: "build", description: "添加依赖" },
+    Gitmoji { emoji: "👷", code: ":construction_worker:", commit_type: "ci", description: "添加或更新 CI 构建系统" },
+    Gitmoji { emoji: "🔧", code: ":wrench:", commit_type: "chore", description: "添加或更新配置文件" },
+    Gitmoji { emoji: "🔖", code: ":bookmark:", commit_type: "chore", description: "发布或版本标签" },
+    Gitmoji { emoji: "⏪️", code: ":rewind:", commit_type: "revert", description: "回退变更" },
+];
+
+/// gitmoji 模式 (`og.gitmoji`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitmojiMode {
+    /// 不使用 gitmoji (默认)
+    Off,
+    /// 按提交类型添加表情，例如 `✨ feat: subject` (`og.gitmoji = type`)
+    Type,
+    /// 以 gitmoji 选择器代替类型选择，例如 `:sparkles: subject` (`og.gitmoji = gitmoji`)
+    Picker,
+}
+
+/// 读取 gitmoji 模式
+pub fn mode() -> Result<GitmojiMode> {
+    Ok(match config::get("og.gitmoji")?.as_deref() {
+        Some("type") => GitmojiMode::Type,
+        Some("gitmoji") => GitmojiMode::Picker,
+        _ => GitmojiMode::Off,
+    })
+}
+
+/// 获取提交类型对应的默认 gitmoji
+pub fn for_type(commit_type: &str) -> Option<&'static Gitmoji> {
+    GITMOJIS.iter().find(|g| g.commit_type == commit_type)
+}
+
+/// 去掉标题开头的 gitmoji (表情符号或短代码)
+///
+/// # 参数
+/// * `subject` - 提交标题
+///
+/// # 返回值
+/// 返回 (识别到的 gitmoji, 剩余的标题)，没有 gitmoji 时返回 (None, 原标题)
+pub fn strip_prefix(subject: &str) -> (Option<&'static Gitmoji>, &str) {
+    for gitmoji in GITMOJIS {
+        // 表情末尾的变体选择符 (U+FE0F) 可有可无
+        let emoji = gitmoji.emoji.trim_end_matches('\u{fe0f}');
+        let rest = subject
+            .strip_prefix(gitmoji.code)
+            .or_else(|| subject.strip_prefix(emoji).map(|rest| rest.trim_start_matches('\u{fe0f}')));
+
+        if let Some(rest) = rest.and_then(|rest| rest.strip_prefix(' ')) {
+            return (Some(gitmoji), rest.trim_start());
+        }
+    }
+
+    (None, subject)
+}
+
+/// 将文本中的 gitmoji 短代码替换为表情符号
+///
+/// # 参数
+/// * `text` - 要处理的文本
+///
+/// # 返回值
+/// 返回替换后的文本
+pub fn emojify(text: &str) -> String {
+    if !text.contains(':') {
+        return text.to_string();
+    }
+
+    GITMOJIS
+        .iter()
+        .fold(text.to_string(), |text, gitmoji| text.replace(gitmoji.code, gitmoji.emoji))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code_of(subject: &str) -> (Option<&'static str>, &str) {
+        let (gitmoji, rest) = strip_prefix(subject);
+        (gitmoji.map(|g| g.code), rest)
+    }
+
+    #[test]
+    fn strips_emoji_and_shortcode_prefixes() {
+        assert_eq!(code_of("✨ feat: 登录表单"), (Some(":sparkles:"), "feat: 登录表单"));
+        assert_eq!(code_of(":sparkles: 登录表单"), (Some(":sparkles:"), "登录表单"));
+        assert_eq!(code_of(":bug:  多余空格"), (Some(":bug:"), "多余空格"));
+        assert_eq!(code_of("feat: 登录表单"), (None, "feat: 登录表单"));
+        assert_eq!(code_of("✨登录表单"), (None, "✨登录表单"));
+    }
+
+    #[test]
+    fn variation_selector_is_optional() {
+        assert_eq!(code_of("♻️ refactor: 拆分模块"), (Some(":recycle:"), "refactor: 拆分模块"));
+        assert_eq!(code_of("♻ refactor: 拆分模块"), (Some(":recycle:"), "refactor: 拆分模块"));
+        assert_eq!(code_of("⚡️ 缓存"), (Some(":zap:"), "缓存"));
+        assert_eq!(code_of("⚡ 缓存"), (Some(":zap:"), "缓存"));
+        assert_eq!(code_of("🚑 紧急修复"), (Some(":ambulance:"), "紧急修复"));
+    }
+
+    #[test]
+    fn emojifies_known_shortcodes_only() {
+        assert_eq!(emojify(":sparkles: 登录 :bug:"), "✨ 登录 🐛");
+        assert_eq!(emojify("会议时间 10:30 :unknown:"), "会议时间 10:30 :unknown:");
+        assert_eq!(emojify("没有短代码"), "没有短代码");
+    }
+}
@@ -0,0 +1,216 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+
+use crate::config;
+use crate::git;
+
+/// 默认的 Issue 编号格式，匹配 `PROJ-123` 形式的 Jira 编号
+///
+/// 编号必须位于分支名称最后一段的开头 (例如 `feat/PROJ-123-login`)，
+/// 以免描述中的 `UTF-8`、`SHA-256` 等文字被误认为编号。
+const DEFAULT_PATTERN: &str = r"^(?:[^/]+/)*([A-Z][A-Z0-9]+-\d+)\b";
+
+/// Issue 编号在提交标注中的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueMode {
+    /// 添加 `Refs: PROJ-123` 尾注 (默认)
+    Trailer,
+    /// 添加到简短描述之前，例如 `feat: PROJ-123 登录表单`
+    Prefix,
+    /// 不从分支名称中提取
+    Off,
+}
+
+/// 读取 Issue 编号的放置方式 (`og.issue.mode`: trailer、prefix 或 off)
+pub fn mode() -> Result<IssueMode> {
+    Ok(match config::get("og.issue.mode")?.as_deref() {
+        Some("prefix") => IssueMode::Prefix,
+        Some("off") => IssueMode::Off,
+        _ => IssueMode::Trailer,
+    })
+}
+
+/// 读取 Issue 编号的正则表达式 (`og.issue.pattern`)
+///
+/// 正则表达式包含捕获组时，第一个捕获组为 Issue 编号，否则整个匹配为 Issue 编号。
+fn pattern() -> Result<Regex> {
+    let pattern = config::get("og.issue.pattern")?.unwrap_or_else(|| DEFAULT_PATTERN.to_string());
+    Regex::new(&pattern).with_context(|| format!("og.issue.pattern 不是有效的正则表达式: {}", pattern))
+}
+
+/// 从捕获结果中取出 Issue 编号
+fn capture_key<'a>(captures: &regex::Captures<'a>) -> &'a str {
+    captures
+        .get(1)
+        .or_else(|| captures.get(0))
+        .map_or("", |m| m.as_str())
+}
+
+/// 从当前分支名称中提取 Issue 编号
+///
+/// # 返回值
+/// 返回 Issue 编号 (例如: 分支 `feat/PROJ-123-login` 返回 PROJ-123)，未启用或未匹配时返回None
+pub fn branch_issue() -> Result<Option<String>> {
+    if mode()? == IssueMode::Off {
+        return Ok(None);
+    }
+
+    // 空仓库或分离头指针状态下没有可用的分支名称
+    let Ok(branch) = git::current_branch() else {
+        return Ok(None);
+    };
+
+    Ok(pattern()?
+        .captures(&branch)
+        .map(|captures| capture_key(&captures).to_string()))
+}
+
+/// 为新分支的简短描述保留开头 Issue 编号的大小写
+///
+/// 分支名称片段会被转为小写，而 Issue 编号通常区分大小写 (例如 `PROJ-123 登录` 应生成
+/// `PROJ-123-登录` 而不是 `proj-123-登录`)，否则 `branch_issue` 无法识别 og 创建的分支。
+///
+/// # 参数
+/// * `description` - 用户输入的简短描述
+/// * `slug` - 由简短描述生成的分支名称片段
+///
+/// # 返回值
+/// 返回保留编号大小写后的分支名称片段
+pub fn preserve_key_case(description: &str, slug: &str) -> Result<String> {
+    let Some(captures) = pattern()?.captures(description.trim()) else {
+        return Ok(slug.to_string());
+    };
+
+    let key = capture_key(&captures);
+    let lowered = crate::utils::slugify(key);
+    if lowered != key.to_lowercase() || !slug.starts_with(&lowered) {
+        return Ok(slug.to_string());
+    }
+
+    Ok(format!("{}{}", key, &slug[lowered.len()..]))
+}
+
+/// 收集提交消息本身携带的 Issue 编号：`Refs` 尾注和标题开头的编号
+///
+/// 不读取当前分支，以免 `og revert`、`og pick` 等写入的日志条目链接到当前分支的编号。
+///
+/// # 参数
+/// * `title` - 日志条目标题
+/// * `body` - 日志条目正文
+///
+/// # 返回值
+/// 返回去重后的 Issue 编号列表
+fn carried_keys(title: &str, body: &str) -> Result<Vec<String>> {
+    let pattern = pattern()?;
+    let is_key = |text: &str| {
+        pattern
+            .find(text)
+            .is_some_and(|found| found.start() == 0 && found.end() == text.len())
+    };
+
+    let mut keys: Vec<String> = Vec::new();
+    let mut add = |key: &str| {
+        if !keys.iter().any(|k| k == key) {
+            keys.push(key.to_string());
+        }
+    };
+
+    for line in body.lines() {
+        let line = line.trim();
+        if let Some((key, value)) = crate::utils::parse_trailer(line.strip_prefix("- ").unwrap_or(line)) {
+            if key == "Refs" {
+                value.split(',').map(str::trim).filter(|v| is_key(v)).for_each(&mut add);
+            }
+        }
+    }
+
+    let description = match crate::utils::parse_conventional_header(title) {
+        Some(header) => header.description,
+        None => title.to_string(),
+    };
+    if let Some(first) = description.split_whitespace().next().filter(|word| is_key(word)) {
+        add(first);
+    }
+
+    Ok(keys)
+}
+
+/// 生成只匹配完整 Issue 编号的正则表达式，避免 `PROJ-1` 匹配到 `PROJ-12`
+fn key_matcher(key: &str) -> Result<Regex> {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let start = if is_word(key.chars().next()) { r"\b" } else { "" };
+    let end = if is_word(key.chars().last()) { r"\b" } else { "" };
+    Ok(Regex::new(&format!("{}{}{}", start, regex::escape(key), end))?)
+}
+
+/// 将日志条目中的 Issue 编号渲染为 Markdown 链接
+///
+/// 链接地址由 `og.issue.url` 模板生成，模板中的 `{key}` 会被替换为 Issue 编号。
+/// 只链接提交消息携带的编号 (见 `carried_keys`)，正文中恰好形如编号的文字 (例如 `UTF-8`) 保持原样。
+/// 标题保持原样以便按标题匹配日志条目，只出现在标题中的编号会在正文末尾追加一行链接。
+///
+/// # 参数
+/// * `entry` - 日志条目内容，第一行为标题
+///
+/// # 返回值
+/// 返回处理后的条目内容，未配置链接模板时原样返回
+pub fn link_log_entry(entry: &str) -> Result<String> {
+    let Some(template) = config::get("og.issue.url")? else {
+        return Ok(entry.to_string());
+    };
+
+    let (title, body) = entry.split_once('\n').unwrap_or((entry, ""));
+    let keys = carried_keys(title, body)?;
+    if keys.is_empty() {
+        return Ok(entry.to_string());
+    }
+
+    let link = |key: &str| format!("[{}]({})", key, template.replace("{key}", key));
+
+    let mut body = body.to_string();
+    let mut title_only = Vec::new();
+    for key in &keys {
+        let matcher = key_matcher(key)?;
+        if matcher.is_match(&body) {
+            body = matcher.replace_all(&body, regex::NoExpand(&link(key))).into_owned();
+        } else if matcher.is_match(title) {
+            title_only.push(key);
+        }
+    }
+
+    let mut result = title.to_string();
+    if !body.is_empty() {
+        result.push('\n');
+        result.push_str(&body);
+    }
+    for key in title_only {
+        result.push_str(&format!("\n- 关联: {}", link(key)));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_pattern_matches_keys_at_branch_segment_start() {
+        let pattern = Regex::new(DEFAULT_PATTERN).unwrap();
+        let key = |branch: &str| pattern.captures(branch).map(|c| capture_key(&c).to_string());
+
+        assert_eq!(key("feat/PROJ-123-login").as_deref(), Some("PROJ-123"));
+        assert_eq!(key("PROJ-7").as_deref(), Some("PROJ-7"));
+        assert_eq!(key("feat/proj-123-login"), None);
+        assert_eq!(key("fix/utf-8-decoding"), None);
+        assert_eq!(key("fix/decode-UTF-8"), None);
+    }
+
+    #[test]
+    fn key_matcher_respects_boundaries() {
+        let matcher = key_matcher("PROJ-1").unwrap();
+        assert!(matcher.is_match("修复 PROJ-1 的问题"));
+        assert!(!matcher.is_match("PROJ-12"));
+        assert!(key_matcher("#12").unwrap().is_match("Refs: #12"));
+    }
+}
@@ -0,0 +1,248 @@
+use anyhow::{Context, Result};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// 操作日志目录 (位于 .git 目录下，不会被提交)
+const JOURNAL_DIR: &str = "ogit";
+/// 操作日志文件名
+const JOURNAL_FILE: &str = "journal";
+/// 日志文件快照目录名
+const SNAPSHOT_DIR: &str = "snapshots";
+
+/// og 执行过的操作记录
+#[derive(Debug, Clone)]
+pub struct Operation {
+    /// 操作编号 (记录时间戳)
+    pub id: String,
+    /// 操作类型 (commit, reset)
+    pub kind: String,
+    /// 操作前的HEAD
+    pub before: String,
+    /// 操作后的HEAD
+    pub after: String,
+    /// 附加信息：commit 为提交消息，reset 为回退模式
+    pub detail: String,
+}
+
+/// 获取操作日志目录，不存在时自动创建
+fn journal_dir() -> Result<PathBuf> {
+    let dir = crate::git::git_dir()?.join(JOURNAL_DIR);
+    fs::create_dir_all(&dir).context("创建操作日志目录失败")?;
+    Ok(dir)
+}
+
+/// 生成新的操作编号
+pub fn new_id() -> String {
+    chrono::Local::now().format("%Y%m%d%H%M%S%3f").to_string()
+}
+
+/// 记录一次操作
+///
+/// # 参数
+/// * `operation` - 操作记录
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn record(operation: &Operation) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(journal_dir()?.join(JOURNAL_FILE))
+        .context("打开操作日志失败")?;
+
+    writeln!(file, "{}", format_line(operation))?;
+
+    Ok(())
+}
+
+/// 将操作记录格式化为一行
+fn format_line(operation: &Operation) -> String {
+    // 附加信息可能包含多行提交消息，转义换行以保持一行一条记录
+    let detail = operation.detail.replace('\\', "\\\\").replace('\n', "\\n");
+    format!(
+        "{}\t{}\t{}\t{}\t{}",
+        operation.id, operation.kind, operation.before, operation.after, detail
+    )
+}
+
+/// 读取所有操作记录，按时间从旧到新排列
+fn read_all() -> Result<Vec<Operation>> {
+    let path = journal_dir()?.join(JOURNAL_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let operations = fs::read_to_string(path)?
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.splitn(5, '\t').collect();
+            if fields.len() < 5 {
+                return None;
+            }
+            Some(Operation {
+                id: fields[0].to_string(),
+                kind: fields[1].to_string(),
+                before: fields[2].to_string(),
+                after: fields[3].to_string(),
+                detail: unescape(fields[4]),
+            })
+        })
+        .collect();
+
+    Ok(operations)
+}
+
+/// 还原记录时转义的换行和反斜杠
+fn unescape(text: &str) -> String {
+    let mut result = String::new();
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => result.push('\n'),
+                Some(other) => result.push(other),
+                None => result.push('\\'),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// 获取最近一次操作
+///
+/// # 返回值
+/// 返回最近一次操作记录，没有记录时返回None
+pub fn last() -> Result<Option<Operation>> {
+    Ok(read_all()?.pop())
+}
+
+/// 删除最近一次操作记录及其日志文件快照
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn pop_last() -> Result<()> {
+    let mut operations = read_all()?;
+    let Some(operation) = operations.pop() else {
+        return Ok(());
+    };
+
+    let mut file = fs::File::create(journal_dir()?.join(JOURNAL_FILE))?;
+    for op in &operations {
+        writeln!(file, "{}", format_line(op))?;
+    }
+
+    let snapshot = snapshot_dir(&operation.id)?;
+    if snapshot.exists() {
+        fs::remove_dir_all(snapshot)?;
+    }
+
+    Ok(())
+}
+
+/// 获取操作对应的日志文件快照目录
+pub fn snapshot_dir(id: &str) -> Result<PathBuf> {
+    Ok(journal_dir()?.join(SNAPSHOT_DIR).join(id))
+}
+
+/// 保存文件快照，文件不存在时记录为空标记
+///
+/// # 参数
+/// * `id` - 操作编号
+/// * `files` - 要保存的文件路径
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn save_snapshot(id: &str, files: &[&str]) -> Result<()> {
+    let dir = snapshot_dir(id)?;
+    fs::create_dir_all(&dir).context("创建快照目录失败")?;
+
+    for file in files {
+        if PathBuf::from(file).exists() {
+            fs::copy(file, dir.join(file)).with_context(|| format!("保存快照失败: {}", file))?;
+        } else {
+            fs::write(dir.join(format!("{}.missing", file)), "")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 从快照恢复文件
+///
+/// # 参数
+/// * `id` - 操作编号
+/// * `files` - 要恢复的文件路径
+///
+/// # 返回值
+/// 存在快照并已恢复返回true，没有快照返回false
+pub fn restore_snapshot(id: &str, files: &[&str]) -> Result<bool> {
+    let dir = snapshot_dir(id)?;
+    if !dir.exists() {
+        return Ok(false);
+    }
+
+    for file in files {
+        if dir.join(format!("{}.missing", file)).exists() {
+            if PathBuf::from(file).exists() {
+                fs::remove_file(file)?;
+            }
+        } else if dir.join(file).exists() {
+            fs::copy(dir.join(file), file).with_context(|| format!("恢复快照失败: {}", file))?;
+        }
+    }
+
+    Ok(true)
+}
+
+/// 删除未被使用的日志文件快照
+///
+/// # 参数
+/// * `id` - 操作编号
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn discard_snapshot(id: &str) -> Result<()> {
+    let dir = snapshot_dir(id)?;
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+/// 查找 og 创建的提交所记录的提交消息
+///
+/// # 参数
+/// * `hash` - 提交的完整哈希
+///
+/// # 返回值
+/// 返回记录的提交消息，不是 og 创建的提交时返回None
+pub fn find_commit_message(hash: &str) -> Result<Option<String>> {
+    Ok(read_all()?
+        .into_iter()
+        .rev()
+        .find(|op| op.kind == "commit" && op.after == hash)
+        .map(|op| op.detail))
+}
+
+/// 按提交标题查找 og 创建的提交，gitmoji 的短代码和表情形式视为相同
+///
+/// # 参数
+/// * `title` - 提交消息的第一行
+///
+/// # 返回值
+/// 返回最近一个标题匹配的提交哈希，没有记录时返回None
+pub fn find_commit_by_title(title: &str) -> Result<Option<String>> {
+    Ok(read_all()?
+        .into_iter()
+        .rev()
+        .find(|op| {
+            op.kind == "commit"
+                && crate::log_manager::log_title(op.detail.lines().next().unwrap_or("")) == crate::log_manager::log_title(title)
+        })
+        .map(|op| op.after))
+}
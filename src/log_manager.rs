@@ -5,8 +5,8 @@ use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
 
 /// 日志文件常量
-const TODAY_LOG_FILE: &str = "TodayDevelopment.md";
-const MAIN_LOG_FILE: &str = "Development.md";
+pub const TODAY_LOG_FILE: &str = "TodayDevelopment.md";
+pub const MAIN_LOG_FILE: &str = "Development.md";
 
 /// 更新日志文件
 /// 
@@ -42,8 +42,24 @@ fn check_or_create_main_log_file() -> Result<()> {
     Ok(())
 }
 
-/// 获取格式化的提交消息，以适应Markdown格式
-fn format_commit_message_for_markdown(commit_message: &str) -> String {
+/// 获取格式化的提交消息，以适应Markdown格式，gitmoji 短代码渲染为表情，Issue 编号渲染为链接
+fn format_commit_message_for_markdown(commit_message: &str) -> Result<String> {
+    crate::issue::link_log_entry(&crate::gitmoji::emojify(&format_commit_lines(commit_message)))
+}
+
+/// 提交标题在日志中的形式 (gitmoji 短代码渲染为表情)，用于按标题匹配日志条目
+///
+/// # 参数
+/// * `title` - 提交消息的第一行
+///
+/// # 返回值
+/// 返回日志条目中的标题
+pub fn log_title(title: &str) -> String {
+    crate::gitmoji::emojify(title.trim())
+}
+
+/// 整理提交消息的行：去掉空行，合作者尾注合并为一行
+fn format_commit_lines(commit_message: &str) -> String {
     // 先检查提交消息是否已经是多行格式
     if commit_message.contains('\n') {
         // 多行格式，需要缩进保持Markdown的列表结构
@@ -61,20 +77,35 @@ fn format_commit_message_for_markdown(commit_message: &str) -> String {
         let mut formatted = String::new();
         formatted.push_str(title);
         
-        // 添加内容，不添加空行
+        // 添加内容，不添加空行；合作者尾注合并为一行
+        let mut co_authors: Vec<&str> = Vec::new();
         for line in lines.iter().skip(1) {
             // 跳过空行
             if line.trim().is_empty() {
                 continue;
             }
             
+            if let Some(identity) = line.strip_prefix(&format!("{}: ", crate::utils::CO_AUTHOR_KEY)) {
+                co_authors.push(identity.split('<').next().unwrap_or(identity).trim());
+                continue;
+            }
+            
             // 添加换行符
             formatted.push('\n');
             
+            // 尾注行补上列表标记，与正文保持一致的Markdown列表结构
+            if !line.starts_with("- ") && crate::utils::parse_trailer(line).is_some() {
+                formatted.push_str("- ");
+            }
+            
             // 添加内容
             formatted.push_str(line);
         }
         
+        if !co_authors.is_empty() {
+            formatted.push_str(&format!("\n- 合作者: {}", co_authors.join(", ")));
+        }
+        
         formatted
     } else {
         // 单行格式，直接返回
@@ -86,10 +117,10 @@ fn format_commit_message_for_markdown(commit_message: &str) -> String {
 fn create_today_log_file(today: &str, commit_message: &str) -> Result<()> {
     let mut file = File::create(TODAY_LOG_FILE)?;
     writeln!(file, "## {}", today)?;
-    writeln!(file, "")?;
+    writeln!(file)?;
     
     // 格式化提交消息并写入
-    let formatted_message = format_commit_message_for_markdown(commit_message);
+    let formatted_message = format_commit_message_for_markdown(commit_message)?;
     
     // 对于多行消息，我们需要确保正确缩进
     // 将格式化的消息按行分割
@@ -129,7 +160,7 @@ fn update_today_log_file(today: &str, commit_message: &str) -> Result<()> {
         let mut file = OpenOptions::new().append(true).open(TODAY_LOG_FILE)?;
         
         // 格式化提交消息并写入
-        let formatted_message = format_commit_message_for_markdown(commit_message);
+        let formatted_message = format_commit_message_for_markdown(commit_message)?;
         
         // 对于多行消息，我们需要确保正确缩进
         // 将格式化的消息按行分割
@@ -163,7 +194,7 @@ fn update_today_log_file(today: &str, commit_message: &str) -> Result<()> {
         if Path::new(MAIN_LOG_FILE).exists() {
             let today_content = fs::read_to_string(TODAY_LOG_FILE)?;
             let mut main_file = OpenOptions::new().append(true).open(MAIN_LOG_FILE)?;
-            writeln!(main_file, "")?;
+            writeln!(main_file)?;
             write!(main_file, "{}", today_content)?;
         } else {
             fs::copy(TODAY_LOG_FILE, MAIN_LOG_FILE)?;
@@ -200,10 +231,346 @@ fn check_log_file_date(today: &str) -> Result<(bool, usize)> {
         }
         
         // 计数日志条目
-        if date_match && line.trim().starts_with(|c: char| c.is_digit(10)) {
+        if date_match && line.trim().starts_with(|c: char| c.is_ascii_digit()) {
             log_count += 1;
         }
     }
     
     Ok((date_match, log_count))
-} 
\ No newline at end of file
+} 
+
+/// 替换今日日志中与原提交消息对应的条目
+///
+/// 用于修改最近一次提交 (amend) 时保持日志与提交历史一致。
+/// 如果今日日志中找不到对应条目，则按普通提交追加新日志。
+///
+/// # 参数
+/// * `old_message` - 原提交消息
+/// * `new_message` - 新提交消息
+///
+/// # 返回值
+/// 成功返回Ok，失败返回Err
+pub fn replace_today_log_entry(old_message: &str, new_message: &str) -> Result<()> {
+    let today = crate::utils::get_today();
+    let old_title = log_title(old_message.lines().next().unwrap_or(""));
+
+    if !Path::new(TODAY_LOG_FILE).exists() || !check_log_file_date(&today)?.0 {
+        println!("{}", "[WARNING] 今日日志中没有可替换的条目，将追加新日志。".bright_yellow());
+        return update_log_files(new_message);
+    }
+
+    let content = fs::read_to_string(TODAY_LOG_FILE)?;
+    let lines: Vec<&str> = content.lines().collect();
+
+    // 从后往前查找标题匹配的编号条目
+    let entry = find_entries(&lines)
+        .into_iter()
+        .rev()
+        .find(|(_, start, _)| entry_title(lines[*start]) == old_title);
+
+    let Some((number, start, end)) = entry else {
+        println!("{}", "[WARNING] 今日日志中未找到与原提交对应的条目，将追加新日志。".bright_yellow());
+        return update_log_files(new_message);
+    };
+
+    let mut output: Vec<String> = lines[..start].iter().map(|l| l.to_string()).collect();
+    output.extend(format_log_entry(number, new_message)?);
+    output.extend(lines[end..].iter().map(|l| l.to_string()));
+
+    let mut file = File::create(TODAY_LOG_FILE)?;
+    for line in output {
+        writeln!(file, "{}", line)?;
+    }
+
+    println!("{}", format!("[SUCCESS] 已替换 {} 中的第{}条日志", TODAY_LOG_FILE, number).bright_green());
+    Ok(())
+}
+
+/// 将提交消息格式化为带编号的日志条目行
+fn format_log_entry(number: usize, commit_message: &str) -> Result<Vec<String>> {
+    let formatted_message = format_commit_message_for_markdown(commit_message)?;
+    let mut lines = formatted_message.split('\n');
+
+    let mut result = vec![format!("{}. {}", number, lines.next().unwrap_or(""))];
+    for line in lines {
+        if !line.trim().is_empty() {
+            result.push(format!("   {}", line));
+        }
+    }
+    Ok(result)
+}
+
+/// 查找日志内容中的所有编号条目
+///
+/// # 返回值
+/// 返回 (编号, 起始行, 结束行) 列表，结束行不包含在条目内
+fn find_entries(lines: &[&str]) -> Vec<(usize, usize, usize)> {
+    let mut entries: Vec<(usize, usize, usize)> = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        let number = entry_number(line);
+
+        // 新条目或非缩进行 (空行、日期标题) 都会结束当前条目
+        if number.is_some() || !line.starts_with("   ") {
+            if let Some(last) = entries.last_mut() {
+                if last.2 == lines.len() {
+                    last.2 = index;
+                }
+            }
+        }
+
+        if let Some(number) = number {
+            entries.push((number, index, lines.len()));
+        }
+    }
+
+    entries
+}
+
+/// 解析日志条目行的编号，例如 "3. fix: xxx" 返回 3
+fn entry_number(line: &str) -> Option<usize> {
+    let (number, _) = line.split_once(". ")?;
+    number.parse().ok()
+}
+
+/// 获取日志条目行的标题部分
+fn entry_title(line: &str) -> &str {
+    line.split_once(". ").map_or(line, |(_, title)| title).trim()
+}
+
+/// 检查下一次更新日志时是否会因日期变更而将今日日志合并到主日志
+///
+/// # 返回值
+/// 如果会发生合并，返回true，否则返回false
+pub fn will_merge_logs() -> Result<bool> {
+    if !Path::new(TODAY_LOG_FILE).exists() {
+        return Ok(false);
+    }
+
+    let (date_match, _) = check_log_file_date(&crate::utils::get_today())?;
+    Ok(!date_match)
+}
+
+/// 从今日日志中删除与提交消息对应的条目，并重新编号剩余条目
+///
+/// # 参数
+/// * `commit_message` - 提交消息
+///
+/// # 返回值
+/// 找到并删除条目返回true，未找到返回false
+pub fn remove_today_log_entry(commit_message: &str) -> Result<bool> {
+    if !Path::new(TODAY_LOG_FILE).exists() {
+        return Ok(false);
+    }
+
+    let title = log_title(commit_message.lines().next().unwrap_or(""));
+    let content = fs::read_to_string(TODAY_LOG_FILE)?;
+    let lines: Vec<&str> = content.lines().collect();
+
+    let Some((number, start, end)) = find_entries(&lines)
+        .into_iter()
+        .rev()
+        .find(|(_, start, _)| entry_title(lines[*start]) == title)
+    else {
+        return Ok(false);
+    };
+
+    let mut file = File::create(TODAY_LOG_FILE)?;
+    for (index, line) in lines.iter().enumerate() {
+        if (start..end).contains(&index) {
+            continue;
+        }
+
+        // 被删除条目之后的条目编号依次减一
+        match entry_number(line) {
+            Some(n) if index > start && n > number => {
+                writeln!(file, "{}. {}", n - 1, entry_title(line))?;
+            }
+            _ => writeln!(file, "{}", line)?,
+        }
+    }
+
+    println!("{}", format!("[SUCCESS] 已从 {} 中删除第{}条日志", TODAY_LOG_FILE, number).bright_green());
+    Ok(true)
+}
+
+/// 开发日志中的一个条目
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// 所在的日志文件
+    pub file: &'static str,
+    /// 所属日期
+    pub date: String,
+    /// 条目编号
+    pub number: usize,
+    /// 条目标题
+    pub title: String,
+}
+
+/// 处理日志条目的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogEntryAction {
+    /// 删除条目
+    Remove,
+    /// 保留条目并标记为已回退
+    MarkReverted,
+}
+
+/// 查找与提交标题对应的日志条目
+///
+/// 每个标题只匹配最近的一个条目：先查找今日日志，再从后往前查找主日志。
+///
+/// # 参数
+/// * `titles` - 提交标题列表
+///
+/// # 返回值
+/// 返回匹配的日志条目列表
+pub fn find_entries_by_titles(titles: &[String]) -> Result<Vec<LogEntry>> {
+    let mut result: Vec<LogEntry> = Vec::new();
+    let mut remaining: Vec<String> = titles.iter().map(|t| log_title(t)).collect();
+
+    for file in [TODAY_LOG_FILE, MAIN_LOG_FILE] {
+        if remaining.is_empty() || !Path::new(file).exists() {
+            continue;
+        }
+
+        let content = fs::read_to_string(file)?;
+        let lines: Vec<&str> = content.lines().collect();
+
+        for (number, start, _) in find_entries(&lines).into_iter().rev() {
+            let title = entry_title(lines[start]);
+            if let Some(pos) = remaining.iter().position(|t| *t == title) {
+                remaining.remove(pos);
+                result.push(LogEntry {
+                    file,
+                    date: section_date(&lines, start),
+                    number,
+                    title: title.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// 获取条目所属的日期标题
+fn section_date(lines: &[&str], index: usize) -> String {
+    lines[..index]
+        .iter()
+        .rev()
+        .find_map(|line| line.strip_prefix("## "))
+        .unwrap_or("")
+        .trim()
+        .to_string()
+}
+
+/// 删除或标记日志条目
+///
+/// 删除条目后会重新编号所在日期下的剩余条目。
+///
+/// # 参数
+/// * `entries` - 要处理的日志条目
+/// * `action` - 处理方式
+///
+/// # 返回值
+/// 返回实际处理的条目数
+pub fn apply_entry_action(entries: &[LogEntry], action: LogEntryAction) -> Result<usize> {
+    let mut changed = 0;
+
+    for file in [TODAY_LOG_FILE, MAIN_LOG_FILE] {
+        let targets: Vec<&LogEntry> = entries.iter().filter(|e| e.file == file).collect();
+        if targets.is_empty() || !Path::new(file).exists() {
+            continue;
+        }
+
+        let content = fs::read_to_string(file)?;
+        let lines: Vec<&str> = content.lines().collect();
+
+        // 定位要处理的条目所在的行范围
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for (number, start, end) in find_entries(&lines) {
+            let date = section_date(&lines, start);
+            if targets.iter().any(|t| t.number == number && t.date == date && t.title == entry_title(lines[start])) {
+                ranges.push((start, end));
+            }
+        }
+
+        if ranges.is_empty() {
+            continue;
+        }
+        changed += ranges.len();
+
+        let mut output: Vec<String> = Vec::new();
+        // 当前日期下已删除的条目数，用于重新编号后续条目
+        let mut removed = 0;
+
+        for (index, line) in lines.iter().enumerate() {
+            if line.starts_with("## ") {
+                removed = 0;
+            }
+
+            if let Some(&(start, _)) = ranges.iter().find(|(start, end)| (*start..*end).contains(&index)) {
+                match action {
+                    LogEntryAction::Remove => {
+                        if index == start {
+                            removed += 1;
+                        }
+                        continue;
+                    }
+                    LogEntryAction::MarkReverted => {
+                        if index == start && !entry_title(line).starts_with("~~") {
+                            let number = entry_number(line).unwrap_or(0);
+                            output.push(format!("{}. ~~{}~~ (已回退)", number, entry_title(line)));
+                            continue;
+                        }
+                    }
+                }
+            }
+
+            match entry_number(line) {
+                Some(number) if removed > 0 => output.push(format!("{}. {}", number - removed, entry_title(line))),
+                _ => output.push(line.to_string()),
+            }
+        }
+
+        let mut file_handle = File::create(file)?;
+        for line in output {
+            writeln!(file_handle, "{}", line)?;
+        }
+    }
+
+    Ok(changed)
+}
+
+/// 按日期和编号查找日志条目
+///
+/// # 参数
+/// * `date` - 日期 (例如: 2025/03/30)
+/// * `number` - 条目编号
+///
+/// # 返回值
+/// 返回找到的日志条目，不存在时返回None
+pub fn find_entry(date: &str, number: usize) -> Result<Option<LogEntry>> {
+    for file in [TODAY_LOG_FILE, MAIN_LOG_FILE] {
+        if !Path::new(file).exists() {
+            continue;
+        }
+
+        let content = fs::read_to_string(file)?;
+        let lines: Vec<&str> = content.lines().collect();
+
+        for (n, start, _) in find_entries(&lines) {
+            if n == number && section_date(&lines, start) == date {
+                return Ok(Some(LogEntry {
+                    file,
+                    date: date.to_string(),
+                    number,
+                    title: entry_title(lines[start]).to_string(),
+                }));
+            }
+        }
+    }
+
+    Ok(None)
+}
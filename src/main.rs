@@ -3,8 +3,14 @@ use clap::{Parser, Subcommand};
 use colored::Colorize;
 use std::path::PathBuf;
 
+mod changelog;
+mod config;
 mod git;
+mod gitmoji;
+mod issue;
+mod journal;
 mod log_manager;
+mod release;
 mod utils;
 
 #[derive(Parser, Debug)]
@@ -33,6 +39,10 @@ struct Cli {
     /// 是否禁用CI构建
     #[arg(short = 'n', long = "no-ci", alias = "nc")]
     no_ci: bool,
+
+    /// 结对的合作者简称，以逗号分隔 (例如: alice,bob)
+    #[arg(long, value_delimiter = ',')]
+    pair: Vec<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -41,6 +51,32 @@ enum Commands {
     Commit(CommitArgs),
     /// 回退到指定的提交
     Reset(ResetArgs),
+    /// 以暂存区的变更创建 fixup! 提交 (不记录日志)
+    Fixup,
+    /// 将 fixup! 提交自动合并到目标提交后推送
+    Squash(SquashArgs),
+    /// 创建 WIP 快照提交 (不记录日志)
+    Wip(WipArgs),
+    /// 管理带说明的储藏
+    Stash(StashArgs),
+    /// 按命名规范创建、切换和查看分支
+    Branch(BranchArgs),
+    /// 获取远程更新、变基到上游分支并推送
+    Sync(SyncArgs),
+    /// 撤销最近一次 og 操作 (提交或回退)
+    Undo,
+    /// 撤销指定提交的变更，生成 revert 提交并记录日志
+    Revert(RevertArgs),
+    /// 从 reflog 和悬空提交中找回丢失的提交
+    Recover,
+    /// 从其他分支挑选提交应用到当前分支
+    Pick(PickArgs),
+    /// 根据 Conventional Commits 计算下一个版本并发布
+    Release(ReleaseArgs),
+    /// 根据 Conventional Commits 历史生成 CHANGELOG.md
+    Changelog(ChangelogArgs),
+    /// 管理结对提交的合作者名单
+    Coauthor(CoauthorArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -64,6 +100,14 @@ struct CommitArgs {
     /// 是否禁用CI构建
     #[arg(short = 'n', long = "no-ci", alias = "nc")]
     no_ci: bool,
+
+    /// 修改最近一次提交，并替换今日日志中对应的条目
+    #[arg(long)]
+    amend: bool,
+
+    /// 结对的合作者简称，以逗号分隔 (例如: alice,bob)
+    #[arg(long, value_delimiter = ',')]
+    pair: Vec<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -71,6 +115,152 @@ struct ResetArgs {
     /// 回退的目标 (例如: HEAD~1, a1b2c3d)
     #[arg(required = false)]
     target: Option<String>,
+
+    /// 从回退前自动创建的备份中恢复
+    #[arg(long, conflicts_with_all = ["target", "to_remote", "to_date", "to_log_entry"])]
+    restore: bool,
+
+    /// 获取远程更新后回退到当前分支的上游分支
+    #[arg(long, conflicts_with_all = ["target", "to_date", "to_log_entry"])]
+    to_remote: bool,
+
+    /// 回退到指定日期 (含当天) 及之前的最后一个提交 (例如: 2025/03/30)
+    #[arg(long, value_name = "DATE", conflicts_with_all = ["target", "to_log_entry"])]
+    to_date: Option<String>,
+
+    /// 回退到开发日志条目对应的提交 (例如: 2025/03/30#2)
+    #[arg(long, value_name = "DATE#N", conflicts_with = "target")]
+    to_log_entry: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+struct SquashArgs {
+    /// 是否推送到远程仓库
+    #[arg(short, long)]
+    push: bool,
+
+    /// 远程仓库名称
+    #[arg(short, long, default_value = "origin")]
+    remote: String,
+}
+
+#[derive(Parser, Debug)]
+struct WipArgs {
+    /// 快照说明
+    #[arg(default_value = None)]
+    message: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+struct StashArgs {
+    #[command(subcommand)]
+    action: StashCommands,
+}
+
+#[derive(Subcommand, Debug)]
+enum StashCommands {
+    /// 储藏当前变更 (包含未跟踪文件)
+    Save,
+    /// 列出所有储藏
+    List,
+    /// 显示储藏的变更统计
+    Show,
+    /// 应用储藏并保留
+    Apply,
+    /// 应用储藏并删除
+    Pop,
+}
+
+#[derive(Parser, Debug)]
+struct BranchArgs {
+    #[command(subcommand)]
+    action: BranchCommands,
+}
+
+#[derive(Subcommand, Debug)]
+enum BranchCommands {
+    /// 按 `类型/简短描述` 规范创建并切换到新分支
+    New,
+    /// 模糊搜索并切换到本地或远程分支
+    Switch,
+    /// 列出本地分支及其领先/落后提交数
+    List,
+    /// 清理已合并或上游已删除的分支
+    Prune(PruneArgs),
+    /// 恢复之前清理掉的分支
+    Restore,
+}
+
+#[derive(Parser, Debug)]
+struct PruneArgs {
+    /// 远程仓库名称
+    #[arg(short, long, default_value = "origin")]
+    remote: String,
+}
+
+#[derive(Parser, Debug)]
+struct SyncArgs {
+    /// 远程仓库名称
+    #[arg(short, long, default_value = "origin")]
+    remote: String,
+}
+
+#[derive(Parser, Debug)]
+struct RevertArgs {
+    /// 要撤销的提交 (例如: HEAD, a1b2c3d)，未指定时从最近的提交中选择
+    #[arg(default_value = None)]
+    target: Option<String>,
+
+    /// 是否推送到远程仓库
+    #[arg(short, long)]
+    push: bool,
+
+    /// 远程仓库名称
+    #[arg(short, long, default_value = "origin")]
+    remote: String,
+}
+
+#[derive(Parser, Debug)]
+struct PickArgs {
+    /// 来源分支，未指定时从分支列表中选择
+    #[arg(default_value = None)]
+    branch: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+struct ReleaseArgs {
+    /// 是否推送提交和标签到远程仓库
+    #[arg(short, long)]
+    push: bool,
+
+    /// 远程仓库名称
+    #[arg(short, long, default_value = "origin")]
+    remote: String,
+}
+
+#[derive(Parser, Debug)]
+struct ChangelogArgs {
+    /// 根据完整历史重新生成整个文件，而不是只更新未发布的变更
+    #[arg(long)]
+    full: bool,
+
+    /// 用于生成提交链接的远程仓库名称
+    #[arg(short, long, default_value = "origin")]
+    remote: String,
+}
+
+#[derive(Parser, Debug)]
+struct CoauthorArgs {
+    #[command(subcommand)]
+    action: CoauthorCommands,
+}
+
+#[derive(Subcommand, Debug)]
+enum CoauthorCommands {
+    /// 列出已配置的合作者
+    List,
+    /// 从提交历史的作者中选择并添加到合作者名单
+    Learn,
 }
 
 /// 获取自适应全屏宽度的分隔线
@@ -130,6 +320,164 @@ fn print_formatted_commit_message(message: &str) {
     }
 }
 
+/// 推送到远程仓库，检测到分支分歧时询问是否强制推送
+///
+/// # 返回值
+/// 推送成功返回true，用户取消强制推送返回false
+fn push_to_remote(remote: &str) -> Result<bool> {
+    // 检查分支是否分歧
+    let mut force_push = false;
+    if git::is_diverged(remote)? {
+        println!("{}", "[WARNING] 检测到本地分支与远程分支存在分歧，可能需要强制推送。".bright_yellow());
+        if utils::confirm("是否要强制推送 (--force-with-lease)？", false)? {
+            force_push = true;
+        } else {
+            return Ok(false);
+        }
+    }
+
+    git::push(remote, force_push).context("推送操作失败")?;
+    println!("{}", format!("[SUCCESS] 成功推送到远程仓库 [{}]", remote).bright_green());
+
+    Ok(true)
+}
+
+/// 处理因冲突暂停的 rebase/revert/cherry-pick 操作
+///
+/// 列出冲突文件，由用户选择解决后继续或放弃操作，直到操作完成或被放弃。
+///
+/// # 参数
+/// * `operation` - 操作名称 (rebase, revert, cherry-pick)
+///
+/// # 返回值
+/// 操作完成返回true，用户放弃返回false
+fn resolve_conflicts(operation: &str) -> Result<bool> {
+    loop {
+        if !wait_for_conflict_resolution(operation)? {
+            return Ok(false);
+        }
+
+        if git::continue_operation(operation)? {
+            return Ok(true);
+        }
+    }
+}
+
+/// 等待用户解决冲突，不自动继续操作
+///
+/// # 参数
+/// * `operation` - 操作名称 (rebase, revert, cherry-pick)
+///
+/// # 返回值
+/// 冲突已全部解决返回true，用户放弃 (已执行 --abort) 返回false
+fn wait_for_conflict_resolution(operation: &str) -> Result<bool> {
+    loop {
+        let files = git::conflicted_files()?;
+
+        println!();
+        println!("{}", format!("[WARNING] git {} 遇到冲突，请在编辑器中解决以下文件的冲突并使用 'git add' 标记为已解决:", operation).bright_yellow());
+        for file in &files {
+            println!("  {}", file.bright_red());
+        }
+        println!();
+
+        let options = ["已解决冲突，继续", "放弃操作并恢复"];
+        let choice = utils::select_option("请选择下一步操作", &options)?;
+
+        if choice == 1 {
+            git::abort_operation(operation)?;
+            println!("{}", format!("[INFO] 已放弃 git {}", operation).bright_blue());
+            return Ok(false);
+        }
+
+        if !git::conflicted_files()?.is_empty() {
+            println!("{}", "[WARNING] 仍有未解决的冲突文件".bright_yellow());
+            continue;
+        }
+
+        return Ok(true);
+    }
+}
+
+/// 提交并记录到操作日志，以便 'og undo' 撤销
+///
+/// 日期变更导致今日日志合并到主日志时，先保存合并前的日志文件快照。
+///
+/// # 参数
+/// * `commit_message` - 提交消息
+fn commit_with_log(commit_message: &str) -> Result<()> {
+    let operation_id = journal::new_id();
+    if log_manager::will_merge_logs()? {
+        journal::save_snapshot(&operation_id, &[log_manager::MAIN_LOG_FILE, log_manager::TODAY_LOG_FILE])?;
+    }
+
+    log_manager::update_log_files(commit_message).context("更新日志文件时出错")?;
+
+    let head_before = git::rev_parse("HEAD").unwrap_or_default();
+    git::commit(commit_message).context("Git提交操作失败")?;
+    let head_after = git::rev_parse("HEAD").unwrap_or_default();
+
+    if head_after != head_before {
+        journal::record(&journal::Operation {
+            id: operation_id,
+            kind: "commit".to_string(),
+            before: head_before,
+            after: head_after,
+            detail: commit_message.to_string(),
+        })?;
+    } else {
+        journal::discard_snapshot(&operation_id)?;
+    }
+
+    Ok(())
+}
+
+/// 撤销一个提交，生成 `revert: <原标题>` 格式的提交并记录到开发日志
+///
+/// # 参数
+/// * `commit` - 要撤销的提交
+///
+/// # 返回值
+/// 回退提交已创建返回true，用户因冲突放弃返回false
+fn revert_commit(commit: &git::CommitInfo) -> Result<bool> {
+    let clean = git::revert_no_commit(&commit.hash, git::is_merge_commit(&commit.hash)?)?;
+
+    // 被撤销的提交中包含当时写入的开发日志，撤销时保留当前日志，只追加 revert 条目
+    restore_log_files()?;
+
+    if !clean && !git::conflicted_files()?.is_empty() && !wait_for_conflict_resolution("revert")? {
+        return Ok(false);
+    }
+
+    commit_with_log(&revert_message(commit))?;
+
+    Ok(true)
+}
+
+/// 生成撤销指定提交的提交消息
+fn revert_message(commit: &git::CommitInfo) -> String {
+    format!("revert: {}\n\nThis reverts commit {}.", commit.subject, commit.hash)
+}
+
+/// 将开发日志文件恢复为HEAD中的版本
+///
+/// revert 和 cherry-pick 会连同提交中的开发日志变更一起应用，导致日志冲突或条目被删除、重复，
+/// 因此在写入新的日志条目前丢弃这些变更 (同时解决日志文件上的冲突)。
+fn restore_log_files() -> Result<()> {
+    let mut paths = Vec::new();
+    for path in [log_manager::TODAY_LOG_FILE, log_manager::MAIN_LOG_FILE] {
+        if git::path_exists_in("HEAD", path)? {
+            paths.push(path);
+        }
+    }
+
+    if !paths.is_empty() {
+        git::checkout_paths("HEAD", &paths).context("恢复开发日志文件失败")?;
+    }
+
+    Ok(())
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
@@ -140,6 +488,45 @@ fn main() -> Result<()> {
         Some(Commands::Reset(args)) => {
             run_reset_workflow(&args)?;
         }
+        Some(Commands::Fixup) => {
+            run_fixup_workflow()?;
+        }
+        Some(Commands::Squash(mut args)) => {
+            run_squash_workflow(&mut args)?;
+        }
+        Some(Commands::Wip(args)) => {
+            run_wip_workflow(&args)?;
+        }
+        Some(Commands::Stash(args)) => {
+            run_stash_workflow(&args)?;
+        }
+        Some(Commands::Branch(args)) => {
+            run_branch_workflow(&args)?;
+        }
+        Some(Commands::Sync(args)) => {
+            run_sync_workflow(&args)?;
+        }
+        Some(Commands::Undo) => {
+            run_undo_workflow()?;
+        }
+        Some(Commands::Revert(mut args)) => {
+            run_revert_workflow(&mut args)?;
+        }
+        Some(Commands::Recover) => {
+            run_recover_workflow()?;
+        }
+        Some(Commands::Pick(args)) => {
+            run_pick_workflow(&args)?;
+        }
+        Some(Commands::Release(mut args)) => {
+            run_release_workflow(&mut args)?;
+        }
+        Some(Commands::Changelog(args)) => {
+            run_changelog_workflow(&args)?;
+        }
+        Some(Commands::Coauthor(args)) => {
+            run_coauthor_workflow(&args)?;
+        }
         None => {
             // Default to commit workflow
             let mut args = CommitArgs {
@@ -148,6 +535,8 @@ fn main() -> Result<()> {
                 remote: cli.remote,
                 ci: cli.ci,
                 no_ci: cli.no_ci,
+                amend: false,
+                pair: cli.pair,
             };
             run_commit_workflow(&mut args)?;
         }
@@ -163,15 +552,30 @@ fn run_reset_workflow(args: &ResetArgs) -> Result<()> {
     println!("{}", separator);
     println!();
 
+    if args.restore {
+        return run_reset_restore();
+    }
+
     // 交互式选择回退模式
     let mode = utils::select_reset_mode()?;
 
-    // 如果命令行未提供目标，则提示用户输入，并提供默认值
-    let target = match &args.target {
-        Some(t) => t.clone(),
-        None => {
-            utils::input_with_default("请输入回退目标 (例如: HEAD~1 或 commit hash)", "HEAD~1")?
-                .unwrap_or_else(|| "HEAD~1".to_string())
+    // 如果命令行未提供目标，则从最近的提交中选择，也可以手动输入
+    let target = match (&args.target, resolve_reset_target(args)?) {
+        (_, Some((target, description))) => {
+            println!("{} {}", "[INFO]".bright_blue(), description.bright_blue());
+            target
+        }
+        (Some(t), None) => t.clone(),
+        (None, None) => {
+            let commits = git::list_commits("HEAD", 100)?;
+            let first_parent = git::first_parent_hashes("HEAD", 100)?;
+            let unpushed = git::unpushed_commits()?;
+
+            match utils::select_reset_target(&commits, &first_parent, &unpushed)? {
+                Some(index) => commits[index].hash.clone(),
+                None => utils::input_with_default("请输入回退目标 (例如: HEAD~1 或 commit hash)", "HEAD~1")?
+                    .unwrap_or_else(|| "HEAD~1".to_string()),
+            }
         }
     };
 
@@ -180,7 +584,81 @@ fn run_reset_workflow(args: &ResetArgs) -> Result<()> {
     println!("目标: {}", target.bright_yellow());
     println!();
 
-    let (confirm_message, default_confirm) = if mode == "hard" {
+    // 预览回退将影响的提交和文件
+    let target_hash = git::rev_parse(&target)?;
+    let removed_commits = git::list_commits(&format!("{}..HEAD", target_hash), 1000)?;
+    let log_titles = removed_commit_log_titles(&removed_commits)?;
+    let log_entries = log_manager::find_entries_by_titles(&log_titles)?;
+    print_reset_preview(&mode, &target_hash, &removed_commits, &log_entries)?;
+
+    // 将被移出分支的提交已经推送时，建议创建回退提交代替改写历史
+    let unpushed = git::unpushed_commits()?;
+    let pushed_commits: Vec<&git::CommitInfo> = removed_commits
+        .iter()
+        .filter(|c| !unpushed.contains(&c.hash))
+        .collect();
+
+    if !pushed_commits.is_empty() {
+        let warning_separator = get_full_width_separator('!', |s| s.bright_red());
+        println!("{}", warning_separator);
+        print_centered_title(
+            &format!("警告: 其中 {} 个提交已推送到远程仓库", pushed_commits.len()),
+            |s| s.bright_red().bold(),
+        );
+        println!("{}", warning_separator);
+        println!("{}", "回退将改写已发布的历史，其他协作者的分支会与远程仓库产生分歧。".bright_red());
+        println!();
+
+        // 只撤销主线上已推送的提交：合并提交以 -m 1 撤销，经由合并引入的提交随合并一并撤销
+        let first_parent = git::first_parent_hashes(&format!("{}..HEAD", target_hash), removed_commits.len())?;
+        let revert_targets: Vec<git::CommitInfo> = pushed_commits
+            .iter()
+            .filter(|c| first_parent.contains(&c.hash))
+            .map(|&c| c.clone())
+            .collect();
+
+        println!("{}", format!("将为以下 {} 个已推送的提交创建 revert 提交:", revert_targets.len()).bright_yellow());
+        for commit in &revert_targets {
+            let merge = if git::is_merge_commit(&commit.hash)? { " [合并提交，以 -m 1 撤销]".bright_magenta().to_string() } else { String::new() };
+            println!("  {} {}{}", commit.short_hash.bright_yellow(), utils::highlight_commit_type(&commit.subject), merge);
+        }
+        if revert_targets.len() < removed_commits.len() {
+            println!("{}", "未推送的提交和经由合并引入的提交不会单独撤销。".bright_blue());
+        }
+        println!();
+
+        if utils::confirm("是否改为为这些提交创建 revert 提交 (不改写历史)?", true)? {
+            return revert_commits(&revert_targets);
+        }
+        println!();
+    }
+
+    // 询问如何处理与被移出提交对应的日志条目
+    let log_action = if log_entries.is_empty() {
+        None
+    } else {
+        let options = ["删除这些日志条目", "保留日志条目", "保留并标记为已回退"];
+        match utils::select_option("请选择如何处理对应的开发日志条目", &options)? {
+            0 => Some(log_manager::LogEntryAction::Remove),
+            2 => Some(log_manager::LogEntryAction::MarkReverted),
+            _ => None,
+        }
+    };
+    println!();
+
+    // hard 模式会丢弃未提交的变更，先询问是否储藏
+    let mut stashed = false;
+    if mode == "hard" && git::has_changes()? {
+        println!("{}", "[WARNING] 检测到未提交的变更，hard 模式回退将丢失这些变更。".bright_yellow());
+        if utils::confirm("是否先储藏这些变更?", true)? {
+            git::stash_save(&format!("og reset 前自动储藏 ({})", target)).context("储藏操作失败")?;
+            println!("{}", "[SUCCESS] 变更已储藏，可通过 'og stash pop' 恢复".bright_green());
+            stashed = true;
+        }
+        println!();
+    }
+
+    let (confirm_message, default_confirm) = if mode == "hard" && !stashed {
         ("确认执行 hard 模式回退吗? 这将丢失工作区和暂存区的代码！", false)
     } else {
         ("确认执行回退操作吗?", true)
@@ -191,8 +669,32 @@ fn run_reset_workflow(args: &ResetArgs) -> Result<()> {
         return Ok(());
     }
 
+    // 回退前保存HEAD和未提交的变更，以便通过 'og reset --restore' 恢复
+    let backup = git::create_backup(&mode, &target).context("创建回退前备份失败")?;
+    println!("{}", format!("[INFO] 已创建回退前备份 [{}]，可通过 'og reset --restore' 恢复", backup).bright_blue());
+
+    let head_before = git::rev_parse("HEAD")?;
     git::reset(&mode, &target).context("Git回退操作失败")?;
 
+    journal::record(&journal::Operation {
+        id: journal::new_id(),
+        kind: "reset".to_string(),
+        before: head_before,
+        after: git::rev_parse("HEAD")?,
+        detail: mode.clone(),
+    })?;
+
+    // hard 模式下已提交的日志文件会随回退一起恢复，因此回退后重新查找对应条目
+    if let Some(action) = log_action {
+        let entries = log_manager::find_entries_by_titles(&log_titles)?;
+        let changed = log_manager::apply_entry_action(&entries, action)?;
+        if changed > 0 {
+            println!("{}", format!("[SUCCESS] 已处理 {} 条开发日志条目", changed).bright_green());
+        } else {
+            println!("{}", "[INFO] 开发日志中已没有对应的条目".bright_blue());
+        }
+    }
+
     println!();
     println!("{}", separator);
     print_centered_title("回退操作已完成", |s| s.bright_green());
@@ -201,39 +703,1467 @@ fn run_reset_workflow(args: &ResetArgs) -> Result<()> {
     Ok(())
 }
 
-fn run_commit_workflow(args: &mut CommitArgs) -> Result<()> {
-    // 创建自适应全屏分割线
+/// 获取 fixup 可选的目标提交：上游分支之后的提交，未设置上游时取最近的提交
+fn fixup_candidates() -> Result<Vec<git::CommitInfo>> {
+    let range = match git::upstream()? {
+        Some(upstream) => format!("{}..HEAD", upstream),
+        None => {
+            println!("{}", "[INFO] 当前分支未设置上游分支，将列出最近的提交".bright_blue());
+            "HEAD".to_string()
+        }
+    };
+
+    let commits = git::list_commits(&range, 20)?
+        .into_iter()
+        .filter(|c| !c.subject.starts_with("fixup! ") && !c.subject.starts_with("squash! "))
+        .collect();
+
+    Ok(commits)
+}
+
+fn run_fixup_workflow() -> Result<()> {
+    let separator = get_full_width_separator('=', |s| s.bright_green());
+    println!("{}", separator);
+    print_centered_title("创建 fixup 提交", |s| s.bright_green());
+    println!("{}", separator);
+    println!();
+
+    if !git::has_staged_changes()? {
+        println!("{}", "[WARNING] 暂存区没有变更，请先使用 'git add' 暂存要修正的内容".bright_yellow());
+        return Ok(());
+    }
+
+    let commits = fixup_candidates()?;
+    if commits.is_empty() {
+        println!("{}", "[WARNING] 没有可以修正的本地提交".bright_yellow());
+        return Ok(());
+    }
+
+    let index = utils::select_commit("请选择要修正的目标提交", &commits)?;
+    let target = &commits[index];
+
+    println!();
+    println!("{} {} {}", "目标提交:".bright_yellow(), target.short_hash.bright_yellow(), target.subject);
+    println!("{}", "[INFO] fixup 提交不会记录到开发日志，执行 'og squash' 时将合并到目标提交".bright_blue());
+    println!();
+
+    git::commit_fixup(&target.hash).context("创建 fixup 提交失败")?;
+
+    println!();
+    println!("{}", separator);
+    print_centered_title("fixup 提交已创建", |s| s.bright_green());
+    println!("{}", separator);
+
+    Ok(())
+}
+
+fn run_squash_workflow(args: &mut SquashArgs) -> Result<()> {
     let separator = get_full_width_separator('=', |s| s.bright_green());
     let section_separator = get_full_width_separator('-', |s| s.bright_yellow());
-    
+
     println!("{}", separator);
-    print_centered_title("项目提交与推送助手", |s| s.bright_green());
+    print_centered_title("合并 fixup 提交", |s| s.bright_green());
     println!("{}", separator);
     println!();
 
-    // 始终使用多行输入方式获取提交消息，如果命令行参数中提供了提交消息，则作为默认标题
-    let commit_message = utils::get_multiline_commit_message(args.commit_message.clone())?;
+    let Some(upstream) = git::upstream()? else {
+        println!("{}", "[WARNING] 当前分支未设置上游分支，无法确定变基的基准".bright_yellow());
+        return Ok(());
+    };
+
+    if git::has_changes()? {
+        println!("{}", "[WARNING] 工作区存在未提交的变更，请先提交或暂存后再执行合并".bright_yellow());
+        return Ok(());
+    }
+
+    let commits = git::list_commits(&format!("{}..HEAD", upstream), 100)?;
+    let fixups = commits
+        .iter()
+        .filter(|c| c.subject.starts_with("fixup! ") || c.subject.starts_with("squash! "))
+        .count();
+
+    if fixups == 0 {
+        println!("{}", "[INFO] 上游分支之后没有 fixup! 提交，无需合并".bright_blue());
+    } else {
+        println!("{} {}", "变基基准:".bright_yellow(), upstream);
+        println!("{} {}", "待合并的 fixup 提交:".bright_yellow(), fixups);
+        println!();
+
+        if !utils::confirm("确认执行 autosquash 变基吗?", true)? {
+            println!("操作已取消。");
+            return Ok(());
+        }
+
+        println!();
+        println!("{}", section_separator);
+        print_centered_title("执行 autosquash 变基", |s| s.bright_yellow());
+        println!("{}", section_separator);
+        git::autosquash(&upstream).context("autosquash 变基失败")?;
+        println!("{}", "[SUCCESS] fixup 提交已合并".bright_green());
+    }
 
-    // 如果未通过命令行参数指定，则交互式询问是否推送到远程仓库
     if !args.push {
         args.push = utils::confirm("是否需要推送到远程仓库?", true)?;
     }
 
-    // 如果选择推送到远程仓库，且未通过命令行参数指定远程仓库名称，则询问远程仓库名称
-    if args.push && args.remote == "origin" {
-        if let Some(remote_name) = utils::input_with_default("请输入远程仓库名称", "origin")? {
-            args.remote = remote_name;
+    if args.push {
+        println!();
+        println!("{}", section_separator);
+        print_centered_title("执行推送", |s| s.bright_yellow());
+        println!("{}", section_separator);
+
+        if !push_to_remote(&args.remote)? {
+            println!("操作已取消。");
+            return Ok(());
         }
     }
 
-    // 检查是否存在.github/workflows文件夹并处理CI构建选项
-    let has_workflows = PathBuf::from(".github/workflows").exists();
-    
-    // 确定CI构建选项
-    let ci_enabled = if args.no_ci {
-        false
-    } else if args.ci {
-        true
+    println!();
+    println!("{}", separator);
+    print_centered_title("操作已完成", |s| s.bright_green());
+    println!("{}", separator);
+
+    Ok(())
+}
+
+fn run_wip_workflow(args: &WipArgs) -> Result<()> {
+    let separator = get_full_width_separator('=', |s| s.bright_green());
+    println!("{}", separator);
+    print_centered_title("创建 WIP 快照", |s| s.bright_green());
+    println!("{}", separator);
+    println!();
+
+    if !git::has_changes()? {
+        println!("没有变更需要提交");
+        return Ok(());
+    }
+
+    let message = args.message.clone().unwrap_or_else(|| "work in progress".to_string());
+    let commit_message = format!("{}{}", git::WIP_PREFIX, message);
+
+    println!("{}", "[INFO] WIP 快照不会记录到开发日志，下次推送前可合并为一个正式提交".bright_blue());
+    git::commit(&commit_message).context("创建 WIP 快照失败")?;
+
+    println!();
+    println!("{}", separator);
+    print_centered_title("WIP 快照已创建", |s| s.bright_green());
+    println!("{}", separator);
+
+    Ok(())
+}
+
+fn run_stash_workflow(args: &StashArgs) -> Result<()> {
+    let separator = get_full_width_separator('=', |s| s.bright_green());
+    println!("{}", separator);
+    print_centered_title("储藏管理", |s| s.bright_green());
+    println!("{}", separator);
+    println!();
+
+    match args.action {
+        StashCommands::Save => save_stash()?,
+        StashCommands::List => {
+            for entry in &stash_entries()? {
+                println!("{} {} {}", entry.reference.bright_yellow(), entry.message, format!("({})", entry.date).bright_blue());
+            }
+        }
+        StashCommands::Show => {
+            if let Some(reference) = pick_stash("请选择要查看的储藏")? {
+                git::stash_show(&reference)?;
+            }
+        }
+        StashCommands::Apply => {
+            if let Some(reference) = pick_stash("请选择要应用的储藏")? {
+                git::stash_apply(&reference, false)?;
+                println!("{}", "[SUCCESS] 储藏已应用".bright_green());
+            }
+        }
+        StashCommands::Pop => {
+            if let Some(reference) = pick_stash("请选择要弹出的储藏")? {
+                git::stash_apply(&reference, true)?;
+                println!("{}", "[SUCCESS] 储藏已应用并删除".bright_green());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 询问储藏说明并储藏当前变更
+fn save_stash() -> Result<()> {
+    if !git::has_changes()? {
+        println!("没有变更需要储藏");
+        return Ok(());
+    }
+
+    let mut message = String::new();
+    while message.is_empty() {
+        message = utils::get_input("请输入储藏说明: ")?;
+        if message.is_empty() {
+            println!("{}", "储藏说明不能为空，请重新输入。".bright_red());
+        }
+    }
+
+    git::stash_save(&message).context("储藏操作失败")?;
+    println!("{}", "[SUCCESS] 变更已储藏".bright_green());
+    Ok(())
+}
+
+/// 获取储藏列表，没有储藏时给出提示
+fn stash_entries() -> Result<Vec<git::StashEntry>> {
+    let entries = git::stash_list()?;
+    if entries.is_empty() {
+        println!("{}", "[INFO] 当前没有任何储藏".bright_blue());
+    }
+    Ok(entries)
+}
+
+/// 从储藏列表中选择一个储藏
+///
+/// # 参数
+/// * `prompt` - 提示信息
+///
+/// # 返回值
+/// 返回所选储藏的引用，没有储藏时返回None
+fn pick_stash(prompt: &str) -> Result<Option<String>> {
+    let entries = stash_entries()?;
+    if entries.is_empty() {
+        return Ok(None);
+    }
+
+    let index = utils::select_stash(prompt, &entries)?;
+    Ok(Some(entries[index].reference.clone()))
+}
+
+fn run_branch_workflow(args: &BranchArgs) -> Result<()> {
+    let separator = get_full_width_separator('=', |s| s.bright_green());
+    println!("{}", separator);
+    print_centered_title("分支管理", |s| s.bright_green());
+    println!("{}", separator);
+    println!();
+
+    match &args.action {
+        BranchCommands::New => {
+            let branch_type = utils::select_commit_type(None)?;
+
+            let name = loop {
+                let description = utils::get_input("请输入分支简短描述: ")?;
+                let slug = issue::preserve_key_case(&description, &utils::slugify(&description))?;
+                if slug.is_empty() {
+                    println!("{}", "简短描述不能为空，请重新输入。".bright_red());
+                    continue;
+                }
+
+                let name = format!("{}/{}", branch_type, slug);
+                if !git::is_valid_branch_name(&name)? {
+                    println!("{}", format!("分支名 [{}] 不合法，请重新输入。", name).bright_red());
+                    continue;
+                }
+                break name;
+            };
+            println!();
+            println!("{} {}", "新分支:".bright_yellow(), name.bright_green());
+
+            if !utils::confirm("确认创建并切换到该分支吗?", true)? {
+                println!("操作已取消。");
+                return Ok(());
+            }
+
+            git::create_branch(&name).context("创建分支失败")?;
+            println!("{}", format!("[SUCCESS] 已切换到新分支 [{}]", name).bright_green());
+        }
+        BranchCommands::Switch => {
+            let branches = git::list_branches(true)?;
+            let local_names: Vec<&str> = branches.iter().filter(|b| !b.is_remote).map(|b| b.name.as_str()).collect();
+
+            // 排除当前分支，以及已有同名本地分支的远程分支
+            let candidates: Vec<git::BranchInfo> = branches
+                .iter()
+                .filter(|b| !b.is_current)
+                .filter(|b| {
+                    !b.is_remote
+                        || !matches!(b.name.split_once('/'), Some((_, local)) if local_names.contains(&local))
+                })
+                .cloned()
+                .collect();
+
+            if candidates.is_empty() {
+                println!("{}", "[INFO] 没有可以切换的其他分支".bright_blue());
+                return Ok(());
+            }
+
+            let index = utils::fuzzy_select_branch("请选择要切换的分支 (输入以搜索)", &candidates)?;
+
+            if git::has_changes()? {
+                println!("{}", "[WARNING] 工作区存在未提交的变更，切换分支时这些变更会被带到目标分支，或因冲突导致切换失败。".bright_yellow());
+                if !utils::confirm("仍要切换分支吗?", false)? {
+                    println!("操作已取消。");
+                    return Ok(());
+                }
+            }
+
+            git::switch_branch(&candidates[index]).context("切换分支失败")?;
+            println!("{}", format!("[SUCCESS] 已切换到分支 [{}]", candidates[index].name).bright_green());
+        }
+        BranchCommands::List => {
+            for branch in git::list_branches(false)? {
+                let marker = if branch.is_current { "*".bright_green() } else { " ".normal() };
+                let tracking = match &branch.upstream {
+                    Some(upstream) => match git::ahead_behind(&branch.name, upstream) {
+                        Ok((ahead, behind)) => format!("↑{} ↓{} {}", ahead, behind, upstream),
+                        Err(_) => format!("{} (上游已不存在)", upstream),
+                    },
+                    None => "无上游分支".to_string(),
+                };
+                println!(
+                    "{} {:<30} {:<30} {}",
+                    marker,
+                    branch.name.bright_yellow(),
+                    tracking.bright_blue(),
+                    branch.date
+                );
+            }
+        }
+        BranchCommands::Prune(prune_args) => {
+            run_branch_prune(prune_args)?;
+        }
+        BranchCommands::Restore => {
+            let refs = git::list_refs(git::PRUNED_REF_PREFIX)?;
+            if refs.is_empty() {
+                println!("{}", "[INFO] 没有可以恢复的已清理分支".bright_blue());
+                return Ok(());
+            }
+
+            let items: Vec<String> = refs
+                .iter()
+                .map(|r| {
+                    let (pruned_at, branch) = split_pruned_name(&r.name);
+                    let pruned_at = pruned_at.map_or(String::new(), |stamp| format!(" [清理于 {}]", format_backup_date(stamp)));
+                    format!("{} {} {} ({}){}", branch.bright_yellow(), &r.hash[..7], r.subject, r.date, pruned_at)
+                })
+                .collect();
+            let selections = utils::multi_select("请选择要恢复的分支", &items)?;
+
+            for index in selections {
+                let pruned = &refs[index];
+                let (_, branch) = split_pruned_name(&pruned.name);
+
+                // 同名分支已存在 (例如已恢复过同名的另一条记录) 时跳过，保留记录以便之后恢复
+                if git::rev_parse(&format!("refs/heads/{}", branch)).is_ok() {
+                    println!("{}", format!("[WARNING] 分支 [{}] 已存在，跳过该记录", branch).bright_yellow());
+                    continue;
+                }
+
+                if let Err(err) = git::create_branch_at(branch, &pruned.hash) {
+                    println!("{}", format!("[WARNING] 恢复分支 [{}] 失败，跳过该记录: {}", branch, err).bright_yellow());
+                    continue;
+                }
+                git::delete_ref(&pruned.refname)?;
+                println!("{}", format!("[SUCCESS] 已恢复分支 [{}]", branch).bright_green());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run_branch_prune(args: &PruneArgs) -> Result<()> {
+    let section_separator = get_full_width_separator('-', |s| s.bright_yellow());
+
+    // 获取远程更新，使上游已删除的分支能被识别
+    if let Err(err) = git::fetch(&args.remote, true) {
+        println!("{}", format!("[WARNING] 获取远程更新失败，将仅根据本地信息判断: {}", err).bright_yellow());
+    }
+
+    let default_branch = git::default_branch(&args.remote)?;
+    let default_local = default_branch
+        .strip_prefix(&format!("{}/", args.remote))
+        .unwrap_or(&default_branch)
+        .to_string();
+    let merged = git::merged_branches(&default_branch)?;
+
+    // 候选分支：已完全合并到默认分支，或上游分支已被删除
+    let candidates: Vec<(git::BranchInfo, &str)> = git::list_branches(false)?
+        .into_iter()
+        .filter(|b| !b.is_current && b.name != default_local)
+        .filter_map(|b| {
+            if b.upstream_gone {
+                Some((b, "上游已删除"))
+            } else if merged.contains(&b.name) {
+                Some((b, "已合并"))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    println!();
+    println!("{} {}", "默认分支:".bright_yellow(), default_branch);
+
+    if candidates.is_empty() {
+        println!("{}", "[INFO] 没有需要清理的分支".bright_blue());
+        return Ok(());
+    }
+
+    let items: Vec<String> = candidates
+        .iter()
+        .map(|(b, reason)| format!("{} [{}] {} ({})", b.name.bright_yellow(), reason, b.subject, b.date))
+        .collect();
+    let selections = utils::multi_select("请选择要删除的分支", &items)?;
+
+    if selections.is_empty() {
+        println!("操作已取消。");
+        return Ok(());
+    }
+
+    // 上游仍然存在的分支可以选择同时删除远程分支
+    let has_remote = selections
+        .iter()
+        .any(|&i| candidates[i].0.upstream.is_some() && !candidates[i].0.upstream_gone);
+    let delete_remote = has_remote && utils::confirm("是否同时删除这些分支对应的远程分支?", false)?;
+
+    println!();
+    println!("{}", section_separator);
+    print_centered_title("删除分支", |s| s.bright_yellow());
+    println!("{}", section_separator);
+
+    // 每次清理的记录放在以时间戳命名的目录下，同名分支再次清理时不会覆盖之前的记录，
+    // 也不会与 `feat` / `feat/x` 这类目录与文件冲突的旧记录互相影响
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let mut record_dir = timestamp.clone();
+    let mut counter = 1;
+    while !git::list_refs(&format!("{}{}/", git::PRUNED_REF_PREFIX, record_dir))?.is_empty() {
+        counter += 1;
+        record_dir = format!("{}-{}", timestamp, counter);
+    }
+
+    for index in selections {
+        let branch = &candidates[index].0;
+
+        // 删除前记录分支末端提交，以便之后恢复
+        let tip = git::rev_parse(&branch.name)?;
+        git::update_ref(
+            &format!("{}{}/{}", git::PRUNED_REF_PREFIX, record_dir, branch.name),
+            &tip,
+            &format!("og branch prune: {}", branch.name),
+        )?;
+
+        git::delete_branch(&branch.name).with_context(|| format!("删除分支 [{}] 失败", branch.name))?;
+        println!("{}", format!("[SUCCESS] 已删除本地分支 [{}] (末端提交 {})", branch.name, &tip[..7]).bright_green());
+
+        if delete_remote && !branch.upstream_gone {
+            if let Some((remote, name)) = branch.upstream.as_deref().and_then(|u| u.split_once('/')) {
+                git::delete_remote_branch(remote, name)
+                    .with_context(|| format!("删除远程分支 [{}] 失败", branch.upstream.as_deref().unwrap_or_default()))?;
+                println!("{}", format!("[SUCCESS] 已删除远程分支 [{}/{}]", remote, name).bright_green());
+            }
+        }
+    }
+
+    println!();
+    println!("{}", "[INFO] 已删除分支的末端提交已记录，可通过 'og branch restore' 恢复".bright_blue());
+
+    Ok(())
+}
+
+fn run_sync_workflow(args: &SyncArgs) -> Result<()> {
+    let separator = get_full_width_separator('=', |s| s.bright_green());
+    let section_separator = get_full_width_separator('-', |s| s.bright_yellow());
+
+    println!("{}", separator);
+    print_centered_title("同步远程仓库", |s| s.bright_green());
+    println!("{}", separator);
+
+    // 1. 获取远程更新
+    println!();
+    println!("{}", section_separator);
+    print_centered_title("获取远程更新", |s| s.bright_yellow());
+    println!("{}", section_separator);
+    git::fetch(&args.remote, false).context("获取远程更新失败")?;
+
+    // 2. 变基到上游分支
+    println!();
+    println!("{}", section_separator);
+    print_centered_title("变基到上游分支", |s| s.bright_yellow());
+    println!("{}", section_separator);
+
+    let upstream = match git::upstream()? {
+        Some(upstream) => Some(upstream),
+        None => {
+            // 未设置上游时，尝试使用远程仓库中的同名分支
+            let candidate = format!("{}/{}", args.remote, git::current_branch()?);
+            if git::rev_parse(&candidate).is_ok() {
+                Some(candidate)
+            } else {
+                None
+            }
+        }
+    };
+
+    match upstream {
+        Some(upstream) => {
+            println!("{} {}", "上游分支:".bright_yellow(), upstream);
+            if git::has_changes()? {
+                println!("{}", "[INFO] 检测到未提交的变更，将在变基前自动储藏并在完成后恢复".bright_blue());
+            }
+
+            if !git::rebase(&upstream, true)? && !resolve_conflicts("rebase")? {
+                println!("操作已取消。");
+                return Ok(());
+            }
+            println!("{}", "[SUCCESS] 变基完成".bright_green());
+        }
+        None => {
+            println!("{}", "[INFO] 远程仓库中还没有当前分支，跳过变基".bright_blue());
+        }
+    }
+
+    // 3. 推送到远程仓库
+    println!();
+    println!("{}", section_separator);
+    print_centered_title("推送到远程仓库", |s| s.bright_yellow());
+    println!("{}", section_separator);
+
+    if !push_to_remote(&args.remote)? {
+        println!("操作已取消。");
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", separator);
+    print_centered_title("同步已完成", |s| s.bright_green());
+    println!("{}", separator);
+
+    Ok(())
+}
+
+fn run_undo_workflow() -> Result<()> {
+    let separator = get_full_width_separator('=', |s| s.bright_red());
+    println!("{}", separator);
+    print_centered_title("撤销最近一次操作", |s| s.bright_red());
+    println!("{}", separator);
+    println!();
+
+    let Some(operation) = journal::last()? else {
+        println!("{}", "[INFO] 没有可以撤销的 og 操作".bright_blue());
+        return Ok(());
+    };
+
+    // HEAD 已经变化说明之后还有其他操作，撤销会丢失这些操作的结果
+    let head = git::rev_parse("HEAD").unwrap_or_default();
+    if head != operation.after {
+        println!("{}", "[WARNING] 无法撤销：最近一次 og 操作之后 HEAD 已发生变化。".bright_yellow());
+        println!("  记录的HEAD: {}", &operation.after[..operation.after.len().min(7)]);
+        println!("  当前的HEAD: {}", &head[..head.len().min(7)]);
+        return Ok(());
+    }
+
+    match operation.kind.as_str() {
+        "commit" => {
+            println!("{} 提交", "操作类型:".bright_yellow());
+            println!("{}", "提交标注:".bright_yellow());
+            print_formatted_commit_message(&operation.detail);
+            println!();
+
+            if operation.before.is_empty() {
+                println!("{}", "[WARNING] 无法撤销：这是仓库的第一个提交。".bright_yellow());
+                return Ok(());
+            }
+
+            if git::is_pushed(&operation.after)? {
+                println!("{}", "[WARNING] 无法撤销：该提交已推送到远程仓库，撤销会改写已发布的历史。".bright_yellow());
+                println!("{}", "如需撤销其变更，请创建一个 revert 提交。".bright_yellow());
+                return Ok(());
+            }
+
+            if !utils::confirm("确认撤销该提交吗? 变更将保留在暂存区", true)? {
+                println!("操作已取消。");
+                return Ok(());
+            }
+
+            git::reset("soft", &operation.before).context("撤销提交失败")?;
+
+            let log_files = [log_manager::MAIN_LOG_FILE, log_manager::TODAY_LOG_FILE];
+            if journal::restore_snapshot(&operation.id, &log_files)? {
+                println!("{}", "[SUCCESS] 已恢复日期变更合并前的开发日志".bright_green());
+            } else if !log_manager::remove_today_log_entry(&operation.detail)? {
+                println!("{}", "[WARNING] 今日日志中未找到对应条目，请手动检查开发日志".bright_yellow());
+            }
+        }
+        "reset" => {
+            let mode = operation.detail.as_str();
+            println!("{} {} 模式回退", "操作类型:".bright_yellow(), mode);
+            println!("{} {}", "回退前的HEAD:".bright_yellow(), &operation.before[..7]);
+            println!();
+
+            if mode == "hard" && git::has_changes()? {
+                println!("{}", "[WARNING] 工作区存在未提交的变更，撤销 hard 模式回退会丢失这些变更！".bright_yellow());
+                if !utils::confirm("仍要撤销吗?", false)? {
+                    println!("操作已取消。");
+                    return Ok(());
+                }
+            } else if !utils::confirm("确认将HEAD移回回退前的提交吗?", true)? {
+                println!("操作已取消。");
+                return Ok(());
+            }
+
+            git::reset(mode, &operation.before).context("撤销回退失败")?;
+        }
+        other => {
+            anyhow::bail!("未知的操作类型: {}", other);
+        }
+    }
+
+    journal::pop_last()?;
+
+    println!();
+    println!("{}", separator);
+    print_centered_title("撤销已完成", |s| s.bright_green());
+    println!("{}", separator);
+
+    Ok(())
+}
+
+/// 解析 --to-remote、--to-date 和 --to-log-entry 指定的回退目标
+///
+/// # 返回值
+/// 返回 (目标提交, 解析说明)，未使用这些选项时返回None
+fn resolve_reset_target(args: &ResetArgs) -> Result<Option<(String, String)>> {
+    if args.to_remote {
+        let upstream = git::upstream()?.context("当前分支未设置上游分支，无法回退到远程状态")?;
+        let remote = upstream.split_once('/').map_or(upstream.as_str(), |(remote, _)| remote);
+        git::fetch(remote, false).context("获取远程更新失败")?;
+
+        let hash = git::rev_parse(&upstream)?;
+        return Ok(Some((hash.clone(), format!("--to-remote 解析为上游分支 {} ({})", upstream, &hash[..7]))));
+    }
+
+    if let Some(date) = &args.to_date {
+        let day = chrono::NaiveDate::parse_from_str(date, "%Y/%m/%d")
+            .with_context(|| format!("日期格式错误: {} (应为 YYYY/MM/DD)", date))?;
+        let commit = git::last_commit_before(&format!("{} 23:59:59", day.format("%Y-%m-%d")))?
+            .with_context(|| format!("{} 及之前没有提交", date))?;
+
+        return Ok(Some((
+            commit.hash.clone(),
+            format!("--to-date 解析为 {} {} ({})", commit.short_hash, commit.subject, commit.date),
+        )));
+    }
+
+    if let Some(spec) = &args.to_log_entry {
+        let (date, number) = spec
+            .split_once('#')
+            .and_then(|(date, number)| Some((date.trim(), number.trim().parse::<usize>().ok()?)))
+            .with_context(|| format!("日志条目格式错误: {} (应为 YYYY/MM/DD#N)", spec))?;
+        let entry = log_manager::find_entry(date, number)?
+            .with_context(|| format!("开发日志中找不到条目: {}", spec))?;
+
+        // 优先使用操作日志中按消息记录的提交，其次按提交标题查找；
+        // 记录的提交可能已被修改或变基而不在当前分支上，此时也按标题查找
+        let hash = match journal::find_commit_by_title(&entry.title)? {
+            Some(hash) if git::rev_parse(&hash).is_ok() && git::is_ancestor(&hash, "HEAD")? => hash,
+            _ => git::find_commit_by_subject(&entry.title)?
+                .map(|c| c.hash)
+                .with_context(|| format!("找不到与日志条目对应的提交: {}", entry.title))?,
+        };
+
+        return Ok(Some((hash.clone(), format!("--to-log-entry 解析为 {} ({})", entry.title, &hash[..7]))));
+    }
+
+    Ok(None)
+}
+
+/// 获取被移出分支的提交在开发日志中对应的标题
+///
+/// og 创建的提交使用操作日志中记录的提交消息 (按哈希匹配)，其余提交使用提交标题 (按消息匹配)。
+fn removed_commit_log_titles(commits: &[git::CommitInfo]) -> Result<Vec<String>> {
+    let mut titles = Vec::new();
+
+    for commit in commits {
+        let title = match journal::find_commit_message(&commit.hash)? {
+            Some(message) => message.lines().next().unwrap_or("").trim().to_string(),
+            None => commit.subject.clone(),
+        };
+        titles.push(title);
+    }
+
+    Ok(titles)
+}
+
+/// 显示回退预览：将被移出分支的提交、对应的日志条目，以及 hard 模式下将被丢弃的文件变更
+fn print_reset_preview(
+    mode: &str,
+    target: &str,
+    removed_commits: &[git::CommitInfo],
+    log_entries: &[log_manager::LogEntry],
+) -> Result<()> {
+    let section_separator = get_full_width_separator('-', |s| s.bright_yellow());
+    println!("{}", section_separator);
+    print_centered_title("回退预览", |s| s.bright_yellow());
+    println!("{}", section_separator);
+
+    if removed_commits.is_empty() {
+        println!("{}", "将移出分支的提交: 无".bright_yellow());
+    } else {
+        println!("{}", format!("将移出分支的提交 ({}):", removed_commits.len()).bright_yellow());
+        for commit in removed_commits {
+            println!(
+                "  {} {} ({}, {})",
+                commit.short_hash.bright_yellow(),
+                utils::highlight_commit_type(&commit.subject),
+                commit.author,
+                commit.date
+            );
+        }
+    }
+
+    if !log_entries.is_empty() {
+        println!();
+        println!("{}", format!("对应的开发日志条目 ({}):", log_entries.len()).bright_yellow());
+        for entry in log_entries {
+            println!(
+                "  {} {} 第{}条: {}",
+                entry.file.bright_blue(),
+                entry.date,
+                entry.number,
+                utils::highlight_commit_type(&entry.title)
+            );
+        }
+    }
+
+    if mode == "hard" {
+        println!();
+        let changes = git::diff_numstat("HEAD")?;
+        if changes.is_empty() {
+            println!("{}", "将丢弃的未提交变更: 无".bright_yellow());
+        } else {
+            println!("{}", format!("将丢弃的未提交变更 ({} 个文件):", changes.len()).bright_yellow());
+            for change in &changes {
+                let stat = match (change.added, change.deleted) {
+                    (Some(added), Some(deleted)) => format!("{} {}", format!("+{}", added).bright_green(), format!("-{}", deleted).bright_red()),
+                    _ => "二进制文件".bright_blue().to_string(),
+                };
+                println!("  {} {}", change.path.bright_red(), stat);
+            }
+        }
+
+        // hard 模式会覆盖目标提交中也存在的未跟踪文件，其余未跟踪文件会被保留
+        let mut overwritten = Vec::new();
+        for path in git::untracked_files()? {
+            if git::path_exists_in(target, &path)? {
+                overwritten.push(path);
+            }
+        }
+        if !overwritten.is_empty() {
+            println!("{}", format!("将被覆盖的未跟踪文件 ({}):", overwritten.len()).bright_yellow());
+            for path in &overwritten {
+                let lines = std::fs::read_to_string(path).map(|c| c.lines().count()).ok();
+                match lines {
+                    Some(lines) => println!("  {} {}", path.bright_red(), format!("{} 行", lines).bright_red()),
+                    None => println!("  {} {}", path.bright_red(), "二进制文件".bright_blue()),
+                }
+            }
+        }
+    }
+
+    println!("{}", section_separator);
+    println!();
+
+    Ok(())
+}
+
+/// 为提交依次创建 revert 提交，代替改写已发布的历史
+///
+/// # 参数
+/// * `commits` - 要撤销的提交，按时间从新到旧排列
+fn revert_commits(commits: &[git::CommitInfo]) -> Result<()> {
+    let separator = get_full_width_separator('=', |s| s.bright_red());
+
+    if git::has_changes()? {
+        println!("{}", "[WARNING] 工作区存在未提交的变更，请先提交或储藏后再创建 revert 提交".bright_yellow());
+        return Ok(());
+    }
+
+    for commit in commits {
+        if !revert_commit(commit)? {
+            println!("操作已取消。");
+            return Ok(());
+        }
+        println!("{}", format!("[SUCCESS] 已撤销提交 {}", commit.short_hash).bright_green());
+    }
+
+    println!();
+    println!("{}", separator);
+    print_centered_title("revert 提交已创建", |s| s.bright_green());
+    println!("{}", separator);
+
+    Ok(())
+}
+
+/// 拆分已清理分支的记录名称 (例如 `20240101-120000/feat/login`) 为清理时间和分支名称
+///
+/// 不以时间戳开头的旧记录整体视为分支名称
+fn split_pruned_name(name: &str) -> (Option<&str>, &str) {
+    match name.split_once('/') {
+        Some((stamp, branch))
+            if chrono::NaiveDateTime::parse_from_str(stamp.get(..15).unwrap_or(stamp), "%Y%m%d-%H%M%S").is_ok() =>
+        {
+            (Some(stamp), branch)
+        }
+        _ => (None, name),
+    }
+}
+
+/// 将备份名称 (时间戳及可能的序号) 格式化为可读的日期
+fn format_backup_date(name: &str) -> String {
+    let (timestamp, counter) = match name.get(15..).and_then(|rest| rest.strip_prefix('-')) {
+        Some(counter) => (&name[..15], Some(counter)),
+        None => (name, None),
+    };
+
+    match chrono::NaiveDateTime::parse_from_str(timestamp, "%Y%m%d-%H%M%S") {
+        Ok(date) => match counter {
+            Some(counter) => format!("{} #{}", date.format("%Y/%m/%d %H:%M:%S"), counter),
+            None => date.format("%Y/%m/%d %H:%M:%S").to_string(),
+        },
+        Err(_) => name.to_string(),
+    }
+}
+
+fn run_reset_restore() -> Result<()> {
+    let separator = get_full_width_separator('=', |s| s.bright_red());
+
+    let backups = git::list_refs(git::BACKUP_REF_PREFIX)?;
+    if backups.is_empty() {
+        println!("{}", "[INFO] 没有可以恢复的回退备份".bright_blue());
+        return Ok(());
+    }
+
+    let snapshots = git::list_refs(git::BACKUP_WORKTREE_REF_PREFIX)?;
+
+    // 按备份时间从新到旧排列
+    let mut backups = backups;
+    backups.sort_by(|a, b| b.name.cmp(&a.name));
+
+    let items: Vec<String> = backups
+        .iter()
+        .map(|b| {
+            let has_snapshot = snapshots.iter().any(|s| s.name == b.name);
+            format!(
+                "{} {} {}{}",
+                format_backup_date(&b.name).bright_yellow(),
+                &b.hash[..7],
+                b.subject,
+                if has_snapshot { " [含未提交变更快照]".bright_magenta().to_string() } else { String::new() }
+            )
+        })
+        .collect();
+
+    let items: Vec<&str> = items.iter().map(String::as_str).collect();
+    let index = utils::select_option("请选择要恢复的备份", &items)?;
+    let backup = &backups[index];
+    let snapshot = snapshots.iter().find(|s| s.name == backup.name);
+
+    println!();
+    println!("{} {}", "备份时间:".bright_yellow(), format_backup_date(&backup.name));
+    println!("{} {} {}", "恢复到:".bright_yellow(), &backup.hash[..7], backup.subject);
+    if snapshot.is_some() {
+        println!("{} 恢复后重新应用备份时的未提交变更", "未提交变更:".bright_yellow());
+    }
+    println!();
+
+    if git::has_changes()? {
+        println!("{}", "[WARNING] 工作区存在未提交的变更，恢复备份将覆盖这些变更 (变更会先保存到恢复前备份中)".bright_yellow());
+    }
+
+    if !utils::confirm("确认恢复该备份吗?", false)? {
+        println!("操作已取消。");
+        return Ok(());
+    }
+
+    // 恢复前同样备份当前HEAD和未提交的变更，以便撤回这次恢复
+    let current = git::create_backup("hard", &backup.hash).context("创建恢复前备份失败")?;
+    println!("{}", format!("[INFO] 已创建恢复前备份 [{}]，可通过 'og reset --restore' 恢复", current).bright_blue());
+    git::reset("hard", &backup.hash).context("恢复备份失败")?;
+    if let Some(snapshot) = snapshot {
+        git::stash_apply(&snapshot.hash, false).context("重新应用未提交变更失败")?;
+    }
+
+    println!();
+    println!("{}", separator);
+    print_centered_title("备份已恢复", |s| s.bright_green());
+    println!("{}", separator);
+
+    Ok(())
+}
+
+fn run_revert_workflow(args: &mut RevertArgs) -> Result<()> {
+    let separator = get_full_width_separator('=', |s| s.bright_red());
+    let section_separator = get_full_width_separator('-', |s| s.bright_yellow());
+
+    println!("{}", separator);
+    print_centered_title("撤销提交", |s| s.bright_red());
+    println!("{}", separator);
+    println!();
+
+    if git::has_changes()? {
+        println!("{}", "[WARNING] 工作区存在未提交的变更，请先提交或储藏后再执行撤销".bright_yellow());
+        return Ok(());
+    }
+
+    let commit = match &args.target {
+        Some(target) => {
+            let hash = git::rev_parse(target)?;
+            git::list_commits(&hash, 1)?
+                .pop()
+                .with_context(|| format!("找不到提交: {}", target))?
+        }
+        None => {
+            let commits = git::list_commits("HEAD", 50)?;
+            if commits.is_empty() {
+                println!("{}", "[INFO] 当前分支还没有提交".bright_blue());
+                return Ok(());
+            }
+            let index = utils::select_commit("请选择要撤销的提交", &commits)?;
+            commits[index].clone()
+        }
+    };
+
+    println!();
+    println!("{} {} {}", "撤销提交:".bright_yellow(), commit.short_hash.bright_yellow(), utils::highlight_commit_type(&commit.subject));
+    println!("{}", "提交标注:".bright_yellow());
+    print_formatted_commit_message(&revert_message(&commit));
+    println!();
+
+    if !args.push {
+        args.push = utils::confirm("是否需要推送到远程仓库?", true)?;
+    }
+
+    if !utils::confirm("确认以上设置并继续?", true)? {
+        println!("操作已取消。");
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", section_separator);
+    print_centered_title("执行撤销并记录日志", |s| s.bright_yellow());
+    println!("{}", section_separator);
+
+    if !revert_commit(&commit)? {
+        println!("操作已取消。");
+        return Ok(());
+    }
+    println!("{}", "[SUCCESS] revert 提交已创建".bright_green());
+
+    if args.push {
+        println!();
+        println!("{}", section_separator);
+        print_centered_title("执行推送", |s| s.bright_yellow());
+        println!("{}", section_separator);
+
+        if !push_to_remote(&args.remote)? {
+            println!("操作已取消。");
+            return Ok(());
+        }
+    }
+
+    println!();
+    println!("{}", separator);
+    print_centered_title("操作已完成", |s| s.bright_green());
+    println!("{}", separator);
+
+    Ok(())
+}
+
+fn run_recover_workflow() -> Result<()> {
+    let separator = get_full_width_separator('=', |s| s.bright_green());
+    println!("{}", separator);
+    print_centered_title("找回丢失的提交", |s| s.bright_green());
+    println!("{}", separator);
+    println!();
+
+    println!("{}", "[INFO] 正在扫描 reflog 和悬空提交...".bright_blue());
+    let commits = git::lost_commits(50)?;
+    if commits.is_empty() {
+        println!("{}", "[INFO] 没有发现不在任何分支上的提交".bright_blue());
+        return Ok(());
+    }
+
+    let items: Vec<String> = commits
+        .iter()
+        .map(|c| {
+            let stat = git::commit_shortstat(&c.hash).unwrap_or_default();
+            format!(
+                "{} {} ({}) {}",
+                c.short_hash.bright_yellow(),
+                utils::highlight_commit_type(&c.subject),
+                c.date,
+                stat.bright_black()
+            )
+        })
+        .collect();
+    let items: Vec<&str> = items.iter().map(String::as_str).collect();
+    let index = utils::select_option("请选择要找回的提交", &items)?;
+    let commit = &commits[index];
+
+    println!();
+    println!("{} {} {}", "选中提交:".bright_yellow(), commit.short_hash.bright_yellow(), commit.subject);
+    println!();
+
+    let actions = ["基于该提交创建新分支", "将该提交 cherry-pick 到当前分支", "将当前分支重置到该提交"];
+    match utils::select_option("请选择找回方式", &actions)? {
+        0 => {
+            let default_name = format!("recover/{}", commit.short_hash);
+            let name = utils::input_with_default("请输入新分支名称", &default_name)?.unwrap_or(default_name);
+            git::create_branch_at(&name, &commit.hash).context("创建分支失败")?;
+            println!("{}", format!("[SUCCESS] 已创建分支 [{}]", name).bright_green());
+        }
+        1 => {
+            if !git::cherry_pick(&commit.hash, false)? && !resolve_conflicts("cherry-pick")? {
+                println!("操作已取消。");
+                return Ok(());
+            }
+            println!("{}", "[SUCCESS] 已将提交应用到当前分支".bright_green());
+        }
+        _ => {
+            if git::has_changes()? {
+                println!("{}", "[WARNING] 工作区存在未提交的变更，重置将丢失这些变更！".bright_yellow());
+            }
+            if !utils::confirm("确认将当前分支重置到该提交吗?", false)? {
+                println!("操作已取消。");
+                return Ok(());
+            }
+
+            let backup = git::create_backup("hard", &commit.hash).context("创建回退前备份失败")?;
+            println!("{}", format!("[INFO] 已创建备份 [{}]，可通过 'og reset --restore' 恢复", backup).bright_blue());
+            git::reset("hard", &commit.hash).context("重置分支失败")?;
+            println!("{}", "[SUCCESS] 当前分支已重置到该提交".bright_green());
+        }
+    }
+
+    println!();
+    println!("{}", separator);
+    print_centered_title("操作已完成", |s| s.bright_green());
+    println!("{}", separator);
+
+    Ok(())
+}
+
+/// 将提交的变更应用到当前分支，提交并记录注明来源的开发日志
+///
+/// # 参数
+/// * `commit` - 要应用的提交
+///
+/// # 返回值
+/// 提交已创建返回true，用户因冲突放弃返回false
+fn pick_commit(commit: &git::CommitInfo) -> Result<bool> {
+    let original_message = git::commit_message(&commit.hash)?;
+
+    let clean = git::cherry_pick(&commit.hash, true)?;
+
+    // 来源提交中包含其所在分支的开发日志变更，丢弃这些变更后再写入本分支的日志条目
+    restore_log_files()?;
+
+    if !clean && !git::conflicted_files()?.is_empty() && !wait_for_conflict_resolution("cherry-pick")? {
+        return Ok(false);
+    }
+
+    let commit_message = format!("{}\n\n(cherry picked from commit {})", original_message, commit.hash);
+    commit_with_log(&commit_message)?;
+
+    Ok(true)
+}
+
+fn run_pick_workflow(args: &PickArgs) -> Result<()> {
+    let separator = get_full_width_separator('=', |s| s.bright_green());
+    let section_separator = get_full_width_separator('-', |s| s.bright_yellow());
+
+    println!("{}", separator);
+    print_centered_title("挑选提交", |s| s.bright_green());
+    println!("{}", separator);
+    println!();
+
+    if git::has_changes()? {
+        println!("{}", "[WARNING] 工作区存在未提交的变更，请先提交或储藏后再挑选提交".bright_yellow());
+        return Ok(());
+    }
+
+    let branch = match &args.branch {
+        Some(branch) => branch.clone(),
+        None => {
+            let branches: Vec<git::BranchInfo> = git::list_branches(true)?
+                .into_iter()
+                .filter(|b| !b.is_current)
+                .collect();
+            if branches.is_empty() {
+                println!("{}", "[INFO] 没有其他分支".bright_blue());
+                return Ok(());
+            }
+            let index = utils::fuzzy_select_branch("请选择来源分支 (输入以搜索)", &branches)?;
+            branches[index].name.clone()
+        }
+    };
+
+    let commits = git::commits_not_on_head(&branch)?;
+    if commits.is_empty() {
+        println!("{}", format!("[INFO] 分支 [{}] 上没有当前分支缺少的提交", branch).bright_blue());
+        return Ok(());
+    }
+
+    let items: Vec<String> = commits
+        .iter()
+        .map(|c| format!("{} {} ({}, {})", c.short_hash.bright_yellow(), utils::highlight_commit_type(&c.subject), c.author, c.date))
+        .collect();
+    let selections = utils::multi_select(&format!("请选择要从 [{}] 挑选的提交 (将按时间顺序应用)", branch), &items)?;
+
+    if selections.is_empty() {
+        println!("操作已取消。");
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", section_separator);
+    print_centered_title("应用提交并记录日志", |s| s.bright_yellow());
+    println!("{}", section_separator);
+
+    for index in selections {
+        let commit = &commits[index];
+        if !pick_commit(commit)? {
+            println!("操作已取消。");
+            return Ok(());
+        }
+        println!("{}", format!("[SUCCESS] 已应用提交 {} {}", commit.short_hash, commit.subject).bright_green());
+    }
+
+    println!();
+    println!("{}", separator);
+    print_centered_title("操作已完成", |s| s.bright_green());
+    println!("{}", separator);
+
+    Ok(())
+}
+
+fn run_release_workflow(args: &mut ReleaseArgs) -> Result<()> {
+    let separator = get_full_width_separator('=', |s| s.bright_green());
+    let section_separator = get_full_width_separator('-', |s| s.bright_yellow());
+
+    println!("{}", separator);
+    print_centered_title("发布新版本", |s| s.bright_green());
+    println!("{}", separator);
+    println!();
+
+    if git::has_changes()? {
+        println!("{}", "[WARNING] 工作区存在未提交的变更，请先提交或储藏后再发布".bright_yellow());
+        return Ok(());
+    }
+
+    let manifest = release::find_manifest();
+    let last_tag = git::latest_version_tag()?;
+
+    // 当前版本优先取最近的版本标签，其次取清单文件中的版本
+    let current = match &last_tag {
+        Some(tag) => release::Version::parse(tag),
+        None => match manifest {
+            Some(manifest) => release::read_manifest_version(manifest)?,
+            None => None,
+        },
+    }
+    .unwrap_or(release::Version { major: 0, minor: 0, patch: 0 });
+
+    let range = match &last_tag {
+        Some(tag) => format!("{}..HEAD", tag),
+        None => "HEAD".to_string(),
+    };
+    let commits = git::commit_messages(&range)?;
+
+    println!("{} {}", "上一个版本:".bright_yellow(), last_tag.as_deref().unwrap_or("无 (未找到版本标签)"));
+    println!("{} {}", "当前版本:".bright_yellow(), current);
+    println!("{}", format!("自上一个版本以来的提交 ({}):", commits.len()).bright_yellow());
+
+    let mut bump: Option<release::Bump> = None;
+    for (hash, message) in &commits {
+        let commit_bump = release::commit_bump(message);
+        let subject = message.lines().next().unwrap_or("");
+        let label = commit_bump.map_or("-".to_string(), |b| b.to_string());
+        println!("  {} {:<5} {}", hash[..7].bright_yellow(), label, utils::highlight_commit_type(subject));
+        bump = bump.max(commit_bump);
+    }
+    println!();
+
+    let Some(bump) = bump else {
+        println!("{}", "[INFO] 没有 feat、fix、perf 或破坏性变更的提交，无需发布新版本".bright_blue());
+        return Ok(());
+    };
+
+    let next = current.bump(bump);
+    let tag = format!("v{}", next);
+    let commit_message = format!("chore(release): {}", tag);
+
+    println!("{} {} → {} ({})", "建议版本:".bright_yellow(), current, next.to_string().bright_green(), bump);
+    match manifest {
+        Some(manifest) => println!("{} {}", "更新版本字段:".bright_yellow(), manifest),
+        None => println!("{} {}", "更新版本字段:".bright_yellow(), "未找到 Cargo.toml 或 package.json，跳过".bright_blue()),
+    }
+    println!("{} {}", "提交标注:".bright_yellow(), commit_message);
+    println!("{} {}", "附注标签:".bright_yellow(), tag);
+    println!();
+
+    if !args.push {
+        args.push = utils::confirm("是否推送提交和标签到远程仓库?", false)?;
+    }
+
+    if !utils::confirm("确认发布该版本吗?", true)? {
+        println!("操作已取消。");
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", section_separator);
+    print_centered_title("更新版本并创建标签", |s| s.bright_yellow());
+    println!("{}", section_separator);
+
+    if let Some(manifest) = manifest {
+        for file in release::update_manifest_version(manifest, next)? {
+            println!("{}", format!("[SUCCESS] 已更新 {}", file).bright_green());
+        }
+    }
+
+    commit_with_log(&commit_message)?;
+    git::create_tag(&tag, &format!("Release {}", tag)).context("创建标签失败")?;
+    println!("{}", format!("[SUCCESS] 已创建标签 [{}]", tag).bright_green());
+
+    if args.push {
+        println!();
+        println!("{}", section_separator);
+        print_centered_title("执行推送", |s| s.bright_yellow());
+        println!("{}", section_separator);
+
+        if !push_to_remote(&args.remote)? {
+            println!("操作已取消。");
+            return Ok(());
+        }
+        git::push_tag(&args.remote, &tag)?;
+        println!("{}", format!("[SUCCESS] 已推送标签 [{}]", tag).bright_green());
+    }
+
+    println!();
+    println!("{}", separator);
+    print_centered_title(&format!("{} 发布完成", tag), |s| s.bright_green());
+    println!("{}", separator);
+
+    Ok(())
+}
+
+fn run_changelog_workflow(args: &ChangelogArgs) -> Result<()> {
+    let separator = get_full_width_separator('=', |s| s.bright_green());
+    let section_separator = get_full_width_separator('-', |s| s.bright_yellow());
+
+    println!("{}", separator);
+    print_centered_title("生成 CHANGELOG", |s| s.bright_green());
+    println!("{}", separator);
+    println!();
+
+    let repo_url = git::remote_web_url(&args.remote)?;
+    match &repo_url {
+        Some(url) => println!("{} {}", "提交链接:".bright_yellow(), url),
+        None => println!("{} {}", "提交链接:".bright_yellow(), format!("未找到远程仓库 [{}]，不生成链接", args.remote).bright_blue()),
+    }
+
+    let existing = std::fs::read_to_string(changelog::CHANGELOG_FILE).ok();
+
+    // 文件不存在时生成完整内容，否则只更新未发布的变更
+    let content = if args.full || existing.is_none() {
+        println!("{} 根据完整历史重新生成", "生成方式:".bright_yellow());
+        changelog::render_full(repo_url.as_deref())?
+    } else {
+        println!("{} 补充新版本并更新未发布的变更", "生成方式:".bright_yellow());
+        changelog::render_incremental(existing.as_deref().unwrap_or_default(), repo_url.as_deref())?
+    };
+
+    if existing.as_deref() == Some(content.as_str()) {
+        println!();
+        println!("{}", format!("[INFO] {} 已是最新", changelog::CHANGELOG_FILE).bright_blue());
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", section_separator);
+    print_centered_title("预览", |s| s.bright_yellow());
+    println!("{}", section_separator);
+    for line in content.lines().take(40) {
+        println!("{}", line);
+    }
+    if content.lines().count() > 40 {
+        println!("{}", "...".bright_blue());
+    }
+    println!("{}", section_separator);
+    println!();
+
+    if !utils::confirm(&format!("确认写入 {} 吗?", changelog::CHANGELOG_FILE), true)? {
+        println!("操作已取消。");
+        return Ok(());
+    }
+
+    std::fs::write(changelog::CHANGELOG_FILE, content).context("写入CHANGELOG失败")?;
+    println!("{}", format!("[SUCCESS] 已更新 {}", changelog::CHANGELOG_FILE).bright_green());
+
+    println!();
+    println!("{}", separator);
+    print_centered_title("操作已完成", |s| s.bright_green());
+    println!("{}", separator);
+
+    Ok(())
+}
+
+fn run_coauthor_workflow(args: &CoauthorArgs) -> Result<()> {
+    let separator = get_full_width_separator('=', |s| s.bright_green());
+    println!("{}", separator);
+    print_centered_title("合作者名单", |s| s.bright_green());
+    println!("{}", separator);
+    println!();
+
+    let roster = config::co_authors()?;
+
+    match &args.action {
+        CoauthorCommands::List => {
+            if roster.is_empty() {
+                println!("{}", "[INFO] 尚未配置合作者，可运行 og coauthor learn 从提交历史中添加".bright_blue());
+                return Ok(());
+            }
+
+            for (handle, identity) in &roster {
+                println!("  {:<12} {}", handle.bright_yellow(), identity);
+            }
+        }
+        CoauthorCommands::Learn => {
+            let own_email = config::get("user.email")?.unwrap_or_default();
+
+            // 排除自己和已在名单中的作者
+            let candidates: Vec<(String, String, usize)> = git::log_authors()?
+                .into_iter()
+                .filter(|(_, email, _)| !email.eq_ignore_ascii_case(&own_email))
+                .filter(|(_, email, _)| {
+                    !roster
+                        .iter()
+                        .any(|(_, identity)| identity.to_lowercase().contains(&format!("<{}>", email.to_lowercase())))
+                })
+                .collect();
+
+            if candidates.is_empty() {
+                println!("{}", "[INFO] 提交历史中没有可以添加的新作者".bright_blue());
+                return Ok(());
+            }
+
+            let items: Vec<String> = candidates
+                .iter()
+                .map(|(name, email, count)| format!("{} <{}> ({} 次提交)", name, email, count))
+                .collect();
+            let selections = utils::multi_select("请选择要添加到名单的作者", &items)?;
+
+            for index in selections {
+                let (name, email, _) = &candidates[index];
+
+                // 默认以邮箱用户名作为简称，git 配置项名称只能包含字母、数字和 `-`
+                let default_handle: String = email
+                    .split('@')
+                    .next()
+                    .unwrap_or_default()
+                    .chars()
+                    .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+                    .collect::<String>()
+                    .to_lowercase();
+                let default_handle = if default_handle.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                    default_handle
+                } else {
+                    format!("u{}", default_handle)
+                };
+
+                let handle = utils::input_with_default(&format!("请输入 {} 的简称", name), &default_handle)?
+                    .unwrap_or(default_handle);
+                config::add_co_author(&handle, &format!("{} <{}>", name, email))
+                    .with_context(|| format!("添加合作者 [{}] 失败", handle))?;
+                println!("{}", format!("[SUCCESS] 已添加合作者 [{}]", handle).bright_green());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run_commit_workflow(args: &mut CommitArgs) -> Result<()> {
+    // 创建自适应全屏分割线
+    let separator = get_full_width_separator('=', |s| s.bright_green());
+    let section_separator = get_full_width_separator('-', |s| s.bright_yellow());
+    
+    println!("{}", separator);
+    print_centered_title("项目提交与推送助手", |s| s.bright_green());
+    println!("{}", separator);
+    println!();
+
+    // 解析 --pair 指定的合作者，未指定时在输入提交标注时从名单中选择
+    let co_authors = if args.pair.is_empty() {
+        None
+    } else {
+        Some(config::resolve_co_authors(&args.pair)?)
+    };
+
+    // 修改最近一次提交时，以HEAD的提交消息为基础进行编辑
+    let amended_message = if args.amend {
+        let head_message = git::head_message()?;
+
+        if git::is_pushed("HEAD")? {
+            println!("{}", "[WARNING] HEAD 提交已推送到远程仓库，修改它将改写已发布的历史，推送时需要强制推送。".bright_yellow());
+            if !utils::confirm("仍要修改最近一次提交吗?", false)? {
+                println!("操作已取消。");
+                return Ok(());
+            }
+        }
+
+        Some(head_message)
+    } else {
+        None
+    };
+
+    // 始终使用多行输入方式获取提交消息，如果命令行参数中提供了提交消息，则作为默认标题
+    let commit_message = match &amended_message {
+        Some(head_message) => utils::edit_existing_commit_message(head_message, co_authors)?,
+        None => utils::get_multiline_commit_message(args.commit_message.clone(), co_authors)?,
+    };
+
+    // 如果未通过命令行参数指定，则交互式询问是否推送到远程仓库
+    if !args.push {
+        args.push = utils::confirm("是否需要推送到远程仓库?", true)?;
+    }
+
+    // 推送前检查HEAD处连续的 WIP 快照，询问是否将其合并到本次提交中
+    let mut squash_wip_count = 0;
+    if args.push && !args.amend {
+        let wip_commits = git::head_wip_commits()?;
+        if !wip_commits.is_empty() {
+            println!("{}", format!("[INFO] 检测到 {} 个尚未推送的 WIP 快照:", wip_commits.len()).bright_blue());
+            for commit in &wip_commits {
+                println!("  {} {}", commit.short_hash.bright_yellow(), commit.subject);
+            }
+            if utils::confirm("是否将这些 WIP 快照合并到本次提交中?", true)? {
+                squash_wip_count = wip_commits.len();
+            }
+        }
+    }
+
+    // 如果选择推送到远程仓库，且未通过命令行参数指定远程仓库名称，则询问远程仓库名称
+    if args.push && args.remote == "origin" {
+        if let Some(remote_name) = utils::input_with_default("请输入远程仓库名称", "origin")? {
+            args.remote = remote_name;
+        }
+    }
+
+    // 检查是否存在.github/workflows文件夹并处理CI构建选项
+    let has_workflows = PathBuf::from(".github/workflows").exists();
+    
+    // 确定CI构建选项
+    let ci_enabled = if args.no_ci {
+        false
+    } else if args.ci {
+        true
     } else if has_workflows {
         if args.push {
             // 如果检测到CI工作流配置且需要推送，提示用户并询问是否进行CI构建
@@ -251,7 +2181,7 @@ fn run_commit_workflow(args: &mut CommitArgs) -> Result<()> {
 
     // 如果不需要CI构建，添加[skip ci]到提交信息
     let final_commit_message = if !ci_enabled && has_workflows {
-        format!("{} [skip ci]", commit_message)
+        utils::append_skip_ci(&commit_message)
     } else {
         commit_message
     };
@@ -263,6 +2193,11 @@ fn run_commit_workflow(args: &mut CommitArgs) -> Result<()> {
     println!("{}", section_separator);
     println!("{}", "提交标注:".bright_yellow());
     print_formatted_commit_message(&final_commit_message);
+    if args.amend {
+        println!("{} {}", "提交方式:".bright_yellow(), "修改最近一次提交 (--amend)".bright_magenta());
+    } else if squash_wip_count > 0 {
+        println!("{} {}", "提交方式:".bright_yellow(), format!("合并 {} 个 WIP 快照", squash_wip_count).bright_magenta());
+    }
     println!();
     
     if args.push {
@@ -293,7 +2228,18 @@ fn run_commit_workflow(args: &mut CommitArgs) -> Result<()> {
     println!("{}", section_separator);
     print_centered_title("开始处理日志", |s| s.bright_yellow());
     println!("{}", section_separator);
-    log_manager::update_log_files(&final_commit_message).context("更新日志文件时出错")?;
+    // 提交前保存日期变更合并前的日志文件，以便 'og undo' 恢复
+    let operation_id = journal::new_id();
+    let will_commit = args.push && !args.amend;
+    if will_commit && log_manager::will_merge_logs()? {
+        journal::save_snapshot(&operation_id, &[log_manager::MAIN_LOG_FILE, log_manager::TODAY_LOG_FILE])?;
+    }
+
+    match &amended_message {
+        Some(head_message) => log_manager::replace_today_log_entry(head_message, &final_commit_message),
+        None => log_manager::update_log_files(&final_commit_message),
+    }
+    .context("更新日志文件时出错")?;
 
     // 执行Git操作
     if args.push {
@@ -303,27 +2249,50 @@ fn run_commit_workflow(args: &mut CommitArgs) -> Result<()> {
         println!("{}", section_separator);
         
         // 提交到本地仓库
+        // 软回退 WIP 快照，使其变更并入本次提交
+        if squash_wip_count > 0 {
+            println!("{}", "[INFO] 正在合并 WIP 快照...".bright_blue());
+            let base = format!("HEAD~{}", squash_wip_count);
+            // 分支上的提交全部是 WIP 快照 (包括根提交) 时没有可回退的基准提交
+            if git::rev_parse(&base).is_ok() {
+                git::reset("soft", &base).context("合并 WIP 快照失败")?;
+            } else if !git::is_shallow()? && git::commit_count("HEAD")? == squash_wip_count {
+                git::unborn_head().context("合并 WIP 快照失败")?;
+            } else {
+                anyhow::bail!("合并 WIP 快照失败: 无法解析 {}", base);
+            }
+        }
+
         println!("{}", "[INFO] 正在添加文件到暂存区并提交到本地仓库...".bright_blue());
-        git::commit(&final_commit_message).context("Git提交操作失败")?;
+        if args.amend {
+            git::commit_amend(&final_commit_message).context("Git修改提交操作失败")?;
+        } else {
+            let head_before = git::rev_parse("HEAD").unwrap_or_default();
+            git::commit(&final_commit_message).context("Git提交操作失败")?;
+            let head_after = git::rev_parse("HEAD").unwrap_or_default();
+
+            // 记录本次提交，以便 'og undo' 撤销
+            if head_after != head_before {
+                journal::record(&journal::Operation {
+                    id: operation_id.clone(),
+                    kind: "commit".to_string(),
+                    before: head_before,
+                    after: head_after,
+                    detail: final_commit_message.clone(),
+                })?;
+            } else {
+                journal::discard_snapshot(&operation_id)?;
+            }
+        }
         println!("{}", "[SUCCESS] Git提交完成".bright_green());
         
         // 推送到远程仓库
         println!("{}", format!("[INFO] 正在推送到远程仓库 [{}]...", args.remote).bright_blue());
         
-        // 检查分支是否分歧
-        let mut force_push = false;
-        if git::is_diverged(&args.remote)? {
-            println!("{}", "[WARNING] 检测到本地分支与远程分支存在分歧，可能需要强制推送。".bright_yellow());
-            if utils::confirm("是否要强制推送 (--force-with-lease)？", false)? {
-                force_push = true;
-            } else {
-                println!("操作已取消。");
-                return Ok(());
-            }
+        if !push_to_remote(&args.remote)? {
+            println!("操作已取消。");
+            return Ok(());
         }
-        
-        git::push(&args.remote, force_push).context("推送操作失败")?;
-        println!("{}", format!("[SUCCESS] 成功推送到远程仓库 [{}]", args.remote).bright_green());
     } else {
         println!("{}", "[INFO] Git操作已禁用，仅更新日志。".bright_blue());
     }
@@ -0,0 +1,367 @@
+use anyhow::{Context, Result};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// 支持更新版本号的项目清单文件
+const MANIFEST_FILES: &[&str] = &["Cargo.toml", "package.json"];
+
+/// 语义化版本号
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    /// 解析 `X.Y.Z` 或 `vX.Y.Z` 格式的版本号，忽略预发布和构建元数据
+    pub fn parse(text: &str) -> Option<Version> {
+        let text = text.trim().trim_start_matches('v');
+        let core = text.split(['-', '+']).next()?;
+        let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+
+        let version = Version {
+            major: parts.next()??,
+            minor: parts.next()??,
+            patch: parts.next()??,
+        };
+
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(version)
+    }
+
+    /// 按变更级别计算下一个版本号
+    pub fn bump(self, bump: Bump) -> Version {
+        match bump {
+            Bump::Major => Version { major: self.major + 1, minor: 0, patch: 0 },
+            Bump::Minor => Version { major: self.major, minor: self.minor + 1, patch: 0 },
+            Bump::Patch => Version { major: self.major, minor: self.minor, patch: self.patch + 1 },
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// 版本变更级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bump::Major => write!(f, "major"),
+            Bump::Minor => write!(f, "minor"),
+            Bump::Patch => write!(f, "patch"),
+        }
+    }
+}
+
+/// 根据单个提交消息判断变更级别
+///
+/// `!` 或 `BREAKING CHANGE` 为 major，`feat` 为 minor，`fix`/`perf` 为 patch，其余类型不触发发布。
+///
+/// # 参数
+/// * `message` - 完整的提交消息
+///
+/// # 返回值
+/// 返回变更级别，不需要发布时返回None
+pub fn commit_bump(message: &str) -> Option<Bump> {
+    let subject = message.lines().next().unwrap_or("");
+    let header = crate::utils::parse_conventional_header(subject)?;
+
+    let breaking_footer = message
+        .lines()
+        .skip(1)
+        .any(|line| line.starts_with("BREAKING CHANGE") || line.starts_with("BREAKING-CHANGE"));
+
+    if header.breaking || breaking_footer {
+        return Some(Bump::Major);
+    }
+
+    match header.commit_type.as_str() {
+        "feat" => Some(Bump::Minor),
+        "fix" | "perf" => Some(Bump::Patch),
+        _ => None,
+    }
+}
+
+/// 查找项目根目录下存在的清单文件
+///
+/// # 返回值
+/// 返回第一个存在的清单文件名称，都不存在时返回None
+pub fn find_manifest() -> Option<&'static str> {
+    MANIFEST_FILES.iter().copied().find(|file| Path::new(file).exists())
+}
+
+/// 读取清单文件中的版本号
+///
+/// # 参数
+/// * `manifest` - 清单文件名称
+///
+/// # 返回值
+/// 返回版本号，找不到版本字段时返回None
+pub fn read_manifest_version(manifest: &str) -> Result<Option<Version>> {
+    let content = fs::read_to_string(manifest).with_context(|| format!("读取 {} 失败", manifest))?;
+    Ok(find_version_line(manifest, &content).and_then(|(_, value)| Version::parse(&value)))
+}
+
+/// 更新清单文件中的版本号，保持其余内容和格式不变
+///
+/// Cargo.toml 会同时更新 Cargo.lock 中本项目的版本号。
+///
+/// # 参数
+/// * `manifest` - 清单文件名称
+/// * `version` - 新版本号
+///
+/// # 返回值
+/// 返回被修改的文件列表
+pub fn update_manifest_version(manifest: &str, version: Version) -> Result<Vec<String>> {
+    let content = fs::read_to_string(manifest).with_context(|| format!("读取 {} 失败", manifest))?;
+    let (updated, old_value) = replace_manifest_version(manifest, &content, version)
+        .with_context(|| format!("{} 中找不到版本字段", manifest))?;
+    fs::write(manifest, updated).with_context(|| format!("写入 {} 失败", manifest))?;
+
+    let mut changed = vec![manifest.to_string()];
+
+    if manifest == "Cargo.toml" && Path::new("Cargo.lock").exists() {
+        if let Some(name) = package_name(&content) {
+            if update_cargo_lock(&name, &old_value, version)? {
+                changed.push("Cargo.lock".to_string());
+            }
+        }
+    }
+
+    Ok(changed)
+}
+
+/// 替换清单文件内容中的版本号
+///
+/// # 参数
+/// * `manifest` - 清单文件名称
+/// * `content` - 清单文件内容
+/// * `version` - 新版本号
+///
+/// # 返回值
+/// 返回 (更新后的内容, 原版本字符串)，找不到版本字段时返回None
+fn replace_manifest_version(manifest: &str, content: &str, version: Version) -> Option<(String, String)> {
+    let (index, old_value) = find_version_line(manifest, content)?;
+
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    lines[index] = lines[index].replacen(&old_value, &version.to_string(), 1);
+
+    Some((join_lines(&lines, content), old_value))
+}
+
+/// 查找清单文件中的版本字段所在行
+///
+/// # 返回值
+/// 返回 (行号, 原版本字符串)
+fn find_version_line(manifest: &str, content: &str) -> Option<(usize, String)> {
+    if manifest == "package.json" {
+        // 只匹配位于顶层对象中、独占一行的 "version" 字段 (不支持整个文件写在一行的紧凑格式)
+        let mut depth = 0;
+        for (index, line) in content.lines().enumerate() {
+            if depth == 1 {
+                if let Some(rest) = line.trim_start().strip_prefix("\"version\"") {
+                    let value = rest.trim_start().strip_prefix(':')?.trim().trim_end_matches(',');
+                    return Some((index, value.trim_matches('"').to_string()));
+                }
+            }
+            depth += json_depth_change(line);
+        }
+        return None;
+    }
+
+    // Cargo.toml 只匹配 [package] 段中的 version 字段
+    let mut in_package = false;
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_package = trimmed == "[package]";
+            continue;
+        }
+        if in_package {
+            if let Some(value) = toml_string_value(trimmed, "version") {
+                return Some((index, value));
+            }
+        }
+    }
+
+    None
+}
+
+/// 计算一行 JSON 中对象和数组的嵌套层级变化，忽略字符串中的括号
+fn json_depth_change(line: &str) -> isize {
+    let mut change = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in line.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '{' | '[' if !in_string => change += 1,
+            '}' | ']' if !in_string => change -= 1,
+            _ => {}
+        }
+    }
+
+    change
+}
+
+/// 读取 `key = "value"` 形式的字符串值
+fn toml_string_value(line: &str, key: &str) -> Option<String> {
+    let rest = line.strip_prefix(key)?.trim_start().strip_prefix('=')?.trim();
+    Some(rest.trim_matches('"').to_string())
+}
+
+/// 读取 Cargo.toml 中的包名称
+fn package_name(content: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_package = trimmed == "[package]";
+            continue;
+        }
+        if in_package {
+            if let Some(name) = toml_string_value(trimmed, "name") {
+                return Some(name);
+            }
+        }
+    }
+    None
+}
+
+/// 更新 Cargo.lock 中本项目的版本号
+fn update_cargo_lock(name: &str, old_version: &str, version: Version) -> Result<bool> {
+    let content = fs::read_to_string("Cargo.lock")?;
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+
+    let name_line = format!("name = \"{}\"", name);
+    let old_line = format!("version = \"{}\"", old_version);
+
+    let Some(index) = lines
+        .windows(2)
+        .position(|pair| pair[0] == name_line && pair[1] == old_line)
+    else {
+        return Ok(false);
+    };
+
+    lines[index + 1] = format!("version = \"{}\"", version);
+    fs::write("Cargo.lock", join_lines(&lines, &content)).context("写入 Cargo.lock 失败")?;
+
+    Ok(true)
+}
+
+/// 按行重新拼接文件内容，沿用原文件的换行符和末尾换行
+fn join_lines(lines: &[String], original: &str) -> String {
+    let newline = if original.contains("\r\n") { "\r\n" } else { "\n" };
+
+    let mut content = lines.join(newline);
+    if original.ends_with('\n') {
+        content.push_str(newline);
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_versions() {
+        assert_eq!(Version::parse("1.2.3"), Some(Version { major: 1, minor: 2, patch: 3 }));
+        assert_eq!(Version::parse("v0.10.0"), Some(Version { major: 0, minor: 10, patch: 0 }));
+        assert_eq!(Version::parse("2.0.0-beta.1+build.5"), Some(Version { major: 2, minor: 0, patch: 0 }));
+        assert_eq!(Version::parse("1.2"), None);
+        assert_eq!(Version::parse("1.2.3.4"), None);
+        assert_eq!(Version::parse("release"), None);
+    }
+
+    #[test]
+    fn bumps_versions() {
+        let version = Version::parse("1.4.2").unwrap();
+        assert_eq!(version.bump(Bump::Major).to_string(), "2.0.0");
+        assert_eq!(version.bump(Bump::Minor).to_string(), "1.5.0");
+        assert_eq!(version.bump(Bump::Patch).to_string(), "1.4.3");
+    }
+
+    #[test]
+    fn detects_commit_bumps() {
+        assert_eq!(commit_bump("feat: 登录表单"), Some(Bump::Minor));
+        assert_eq!(commit_bump("fix(parser): 空行"), Some(Bump::Patch));
+        assert_eq!(commit_bump("perf: 缓存"), Some(Bump::Patch));
+        assert_eq!(commit_bump("docs: 更新 README"), None);
+        assert_eq!(commit_bump("更新依赖"), None);
+    }
+
+    #[test]
+    fn detects_breaking_changes() {
+        assert_eq!(commit_bump("feat!: 移除旧接口"), Some(Bump::Major));
+        assert_eq!(commit_bump("fix(api)!: 修改返回值"), Some(Bump::Major));
+        assert_eq!(commit_bump("refactor: 重写配置\n\nBREAKING CHANGE: 配置项已改名"), Some(Bump::Major));
+        assert_eq!(commit_bump("feat: 新参数\n- 说明\n\nBREAKING-CHANGE: 默认值改变"), Some(Bump::Major));
+        assert_eq!(commit_bump(":sparkles: feat(parser)!: 新语法"), Some(Bump::Major));
+        assert_eq!(commit_bump("✨ 新功能"), Some(Bump::Minor));
+    }
+
+    #[test]
+    fn rewrites_only_package_version_in_cargo_toml() {
+        let content = "[package]\nname = \"og\"\nversion = \"0.1.0\"\n\n[dependencies]\nclap = { version = \"0.1.0\" }\n\n[dev-dependencies.foo]\nversion = \"0.1.0\"\n";
+        let (updated, old) = replace_manifest_version("Cargo.toml", content, Version::parse("0.2.0").unwrap()).unwrap();
+
+        assert_eq!(old, "0.1.0");
+        assert_eq!(updated, content.replacen("version = \"0.1.0\"\n\n[dependencies]", "version = \"0.2.0\"\n\n[dependencies]", 1));
+    }
+
+    #[test]
+    fn skips_versions_outside_package_section() {
+        let content = "[workspace.package]\nversion = \"1.0.0\"\n\n[package]\nname = \"og\"\nversion = \"0.3.1\"\n";
+        let (updated, old) = replace_manifest_version("Cargo.toml", content, Version::parse("0.4.0").unwrap()).unwrap();
+
+        assert_eq!(old, "0.3.1");
+        assert!(updated.contains("version = \"1.0.0\""));
+        assert!(updated.ends_with("version = \"0.4.0\"\n"));
+        assert!(replace_manifest_version("Cargo.toml", "[workspace]\nmembers = []\n", Version::parse("1.0.0").unwrap()).is_none());
+    }
+
+    #[test]
+    fn keeps_crlf_line_endings() {
+        let content = "[package]\r\nname = \"og\"\r\nversion = \"1.0.0\"\r\n";
+        let (updated, _) = replace_manifest_version("Cargo.toml", content, Version::parse("1.0.1").unwrap()).unwrap();
+        assert_eq!(updated, "[package]\r\nname = \"og\"\r\nversion = \"1.0.1\"\r\n");
+    }
+
+    #[test]
+    fn rewrites_package_json_version() {
+        let content = "{\n  \"name\": \"app\",\n  \"version\": \"1.2.3\",\n  \"dependencies\": {}\n}\n";
+        let (updated, old) = replace_manifest_version("package.json", content, Version::parse("1.3.0").unwrap()).unwrap();
+
+        assert_eq!(old, "1.2.3");
+        assert_eq!(updated, content.replace("1.2.3", "1.3.0"));
+    }
+
+    #[test]
+    fn skips_nested_package_json_versions() {
+        let content = "{\n  \"name\": \"app {\\\"x\",\n  \"engines\": {\n    \"version\": \"0.9.0\"\n  },\n  \"version\": \"1.2.3\"\n}\n";
+        let (updated, old) = replace_manifest_version("package.json", content, Version::parse("1.3.0").unwrap()).unwrap();
+
+        assert_eq!(old, "1.2.3");
+        assert!(updated.contains("\"version\": \"0.9.0\""));
+        assert!(updated.contains("\"version\": \"1.3.0\""));
+        assert!(replace_manifest_version("package.json", "{\n  \"deps\": {\n    \"version\": \"1.0.0\"\n  }\n}\n", Version::parse("1.0.1").unwrap()).is_none());
+    }
+}
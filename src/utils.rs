@@ -1,24 +1,25 @@
 use anyhow::{Context, Result};
-use dialoguer::{Confirm, theme::ColorfulTheme};
+use dialoguer::{Confirm, FuzzySelect, MultiSelect, Select, theme::ColorfulTheme};
 use rustyline::DefaultEditor;
 use colored::Colorize;
 use std::fmt::Write as FmtWrite;
 
-/// Conventional Commits 类型定义
-const COMMIT_TYPES: &[(&str, &str)] = &[
-    ("feat", "新功能 (A new feature)"),
-    ("fix", "Bug修复 (A bug fix)"),
-    ("docs", "文档变更 (Documentation only changes)"),
-    ("style", "代码风格 (Changes that do not affect the meaning of the code)"),
-    ("refactor", "代码重构 (A code change that neither fixes a bug nor adds a feature)"),
-    ("perf", "性能优化 (A code change that improves performance)"),
-    ("test", "测试相关 (Adding missing tests or correcting existing tests)"),
-    ("build", "构建系统或外部依赖变更 (Changes that affect the build system or external dependencies)"),
-    ("ci", "CI/CD配置文件和脚本的变更 (Changes to our CI configuration files and scripts)"),
-    ("chore", "其他不修改 src 或 test 文件的变更 (Other changes that don't modify src or test files)"),
-    ("revert", "回退之前的提交 (Reverts a previous commit)"),
-];
+use crate::gitmoji::{Gitmoji, GitmojiMode, GITMOJIS};
 
+/// Conventional Commits 类型定义：(类型, 说明, CHANGELOG 分节标题)
+pub const COMMIT_TYPES: &[(&str, &str, &str)] = &[
+    ("feat", "新功能 (A new feature)", "Features"),
+    ("fix", "Bug修复 (A bug fix)", "Bug Fixes"),
+    ("docs", "文档变更 (Documentation only changes)", "Documentation"),
+    ("style", "代码风格 (Changes that do not affect the meaning of the code)", "Styles"),
+    ("refactor", "代码重构 (A code change that neither fixes a bug nor adds a feature)", "Code Refactoring"),
+    ("perf", "性能优化 (A code change that improves performance)", "Performance"),
+    ("test", "测试相关 (Adding missing tests or correcting existing tests)", "Tests"),
+    ("build", "构建系统或外部依赖变更 (Changes that affect the build system or external dependencies)", "Build System"),
+    ("ci", "CI/CD配置文件和脚本的变更 (Changes to our CI configuration files and scripts)", "Continuous Integration"),
+    ("chore", "其他不修改 src 或 test 文件的变更 (Other changes that don't modify src or test files)", "Chores"),
+    ("revert", "回退之前的提交 (Reverts a previous commit)", "Reverts"),
+];
 /// 获取用户确认
 /// 
 /// # 参数
@@ -35,7 +36,7 @@ pub fn confirm(message: &str, default: bool) -> Result<bool> {
 }
 
 /// 使用rustyline获取单行输入
-fn get_input(prompt: &str) -> Result<String> {
+pub fn get_input(prompt: &str) -> Result<String> {
     let mut rl = DefaultEditor::new().context("无法初始化输入编辑器")?;
     
     // 获取输入
@@ -63,146 +64,658 @@ pub fn input_with_default(message: &str, default: &str) -> Result<Option<String>
     }
 }
 
+/// 破坏性变更尾注的键名
+pub const BREAKING_CHANGE_KEY: &str = "BREAKING CHANGE";
+
+/// 合作者尾注的键名
+pub const CO_AUTHOR_KEY: &str = "Co-authored-by";
+
+/// 使用 `Key #N` 形式书写的关闭 Issue 尾注键名
+const ISSUE_CLOSING_KEYS: &[&str] = &["Closes", "Fixes", "Resolves"];
+
+/// 添加尾注时可选择的常用键名
+const COMMON_FOOTER_KEYS: &[&str] = &["Refs", "Closes", "Reviewed-by", CO_AUTHOR_KEY];
+
+/// 正文模板中的字段，渲染为 `- 标签: 内容` 形式的正文行
+struct TemplateField {
+    label: String,
+    required: bool,
+    value: String,
+}
+
 /// 提交标注内容
 struct CommitContent {
-    title: String,
+    commit_type: String,
+    scope: Option<String>,
+    subject: String,
+    /// 正文模板字段，排在其他正文行之前
+    fields: Vec<TemplateField>,
     content_lines: Vec<String>,
+    /// 破坏性变更说明，Some 时标题带 `!` 标记，说明非空时生成 `BREAKING CHANGE:` 尾注
+    breaking: Option<String>,
+    /// 尾注 (键, 值)，例如 ("Refs", "#12")
+    footers: Vec<(String, String)>,
+    /// 关联的 Issue 编号，例如从分支名称中提取的 PROJ-123
+    issue: Option<String>,
+    /// Issue 编号是否放在简短描述之前，否则生成 `Refs:` 尾注
+    issue_in_title: bool,
+    /// gitmoji 模式
+    gitmoji_mode: GitmojiMode,
+    /// gitmoji 选择器模式下选择的 gitmoji
+    gitmoji: Option<&'static Gitmoji>,
+}
+
+impl CommitContent {
+    /// 创建空的提交标注内容，并读取 gitmoji 模式
+    fn new() -> Result<Self> {
+        Ok(CommitContent {
+            commit_type: String::new(),
+            scope: None,
+            subject: String::new(),
+            fields: Vec::new(),
+            content_lines: Vec::new(),
+            breaking: None,
+            footers: Vec::new(),
+            issue: None,
+            issue_in_title: false,
+            gitmoji_mode: crate::gitmoji::mode()?,
+            gitmoji: None,
+        })
+    }
+
+    /// 组合提交标题 (例如: feat(parser)!: 支持注释、✨ feat: 支持注释 或 :sparkles: 支持注释)
+    ///
+    /// gitmoji 选择器模式下，带作用域或破坏性变更标记的标题保留完整的类型头
+    /// (例如 `:sparkles: feat(parser)!: 支持注释`)，以免丢失作用域和 `!` 标记。
+    fn title(&self) -> String {
+        let issue = match &self.issue {
+            Some(issue) if self.issue_in_title && !self.subject.contains(issue.as_str()) => format!("{} ", issue),
+            _ => String::new(),
+        };
+
+        let emoji = match (self.gitmoji_mode, self.gitmoji) {
+            (GitmojiMode::Picker, Some(gitmoji)) => {
+                if self.scope.is_none() && self.breaking.is_none() {
+                    return format!("{} {}{}", gitmoji.code, issue, self.subject);
+                }
+                format!("{} ", gitmoji.code)
+            }
+            (GitmojiMode::Type, _) => crate::gitmoji::for_type(&self.commit_type).map_or(String::new(), |g| format!("{} ", g.emoji)),
+            _ => String::new(),
+        };
+        let scope = self.scope.as_ref().map_or(String::new(), |scope| format!("({})", scope));
+        let breaking = if self.breaking.is_some() { "!" } else { "" };
+        format!("{}{}{}{}: {}{}", emoji, self.commit_type, scope, breaking, issue, self.subject)
+    }
+
+    /// Issue 编号对应的 `Refs` 尾注，已放在标题中或已有相同尾注时返回None
+    fn issue_footer(&self) -> Option<(String, String)> {
+        let issue = self.issue.as_ref().filter(|_| !self.issue_in_title)?;
+        let exists = self.footers.iter().any(|(key, value)| key == "Refs" && value == issue);
+        (!exists).then(|| ("Refs".to_string(), issue.clone()))
+    }
+
+    /// 按顺序列出所有正文行，已填写的模板字段排在最前
+    fn body_lines(&self) -> Vec<String> {
+        self.fields
+            .iter()
+            .filter(|field| !field.value.is_empty())
+            .map(|field| format!("- {}: {}", field.label, field.value))
+            .chain(self.content_lines.iter().cloned())
+            .collect()
+    }
+
+    /// 按提交类型的模板建立正文字段，已有正文中 `- 标签: 内容` 形式的行会移入对应字段
+    fn apply_template(&mut self) -> Result<()> {
+        let mut fields = Vec::new();
+
+        for (label, required) in crate::config::template(&self.commit_type)? {
+            let prefix = format!("- {}: ", label);
+            let value = match self.fields.iter().position(|f| f.label == label) {
+                Some(index) => self.fields.remove(index).value,
+                None => match self.content_lines.iter().position(|line| line.starts_with(&prefix)) {
+                    Some(index) => self.content_lines.remove(index)[prefix.len()..].trim().to_string(),
+                    None => String::new(),
+                },
+            };
+            fields.push(TemplateField { label, required, value });
+        }
+
+        // 切换类型后新模板中没有的字段保留为普通正文行
+        for field in self.fields.drain(..).filter(|f| !f.value.is_empty()) {
+            self.content_lines.insert(0, format!("- {}: {}", field.label, field.value));
+        }
+        self.fields = fields;
+
+        Ok(())
+    }
+
+    /// 交互式填写模板字段，必填字段不能为空
+    ///
+    /// # 参数
+    /// * `only_missing` - 是否只询问尚未填写的必填字段
+    fn fill_fields(&mut self, only_missing: bool) -> Result<()> {
+        for field in self.fields.iter_mut() {
+            if only_missing && (!field.required || !field.value.is_empty()) {
+                continue;
+            }
+
+            let marker = if field.required { " (必填)" } else { " (可留空)" };
+            loop {
+                field.value = get_input(&format!("{}{}: ", field.label, marker))?;
+                if !field.required || !field.value.is_empty() {
+                    break;
+                }
+                println!("{}", format!("{}为必填项，请重新输入。", field.label).bright_red());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 按顺序列出所有尾注行，破坏性变更说明排在最前
+    fn footer_lines(&self) -> Vec<String> {
+        let breaking = self
+            .breaking
+            .as_ref()
+            .filter(|note| !note.is_empty())
+            .map(|note| format_footer(BREAKING_CHANGE_KEY, note));
+
+        breaking
+            .into_iter()
+            .chain(self.issue_footer().map(|(key, value)| format_footer(&key, &value)))
+            .chain(self.footers.iter().map(|(key, value)| format_footer(key, value)))
+            .collect()
+    }
+}
+
+/// 提交标注编辑菜单中的操作
+enum EditAction {
+    Title,
+    Gitmoji,
+    Scope,
+    Breaking,
+    Issue,
+    IssuePlacement,
+    Field(usize),
+    Line(usize),
+    AddLine,
+    RemoveLastLine,
+    Footer(usize),
+    AddFooter,
+}
+
+/// 格式化一条尾注，关闭 Issue 的尾注使用 `Closes #12` 形式，其余使用 `Key: value` 形式
+///
+/// # 参数
+/// * `key` - 尾注键名
+/// * `value` - 尾注值
+///
+/// # 返回值
+/// 返回尾注行
+fn format_footer(key: &str, value: &str) -> String {
+    if ISSUE_CLOSING_KEYS.contains(&key) && value.starts_with('#') {
+        format!("{} {}", key, value)
+    } else {
+        format!("{}: {}", key, value)
+    }
+}
+
+/// 解析 `Key: value`、`Key #N` 或 `BREAKING CHANGE: ...` 形式的尾注行
+///
+/// `Key #N` 形式只接受关闭 Issue 的键 (`Closes #12`)，`Issue #3` 之类的正文不视为尾注。
+///
+/// # 参数
+/// * `line` - 提交消息中的一行
+///
+/// # 返回值
+/// 返回 (键, 值)，不是尾注时返回None
+pub fn parse_trailer(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+
+    if let Some(note) = line
+        .strip_prefix("BREAKING CHANGE:")
+        .or_else(|| line.strip_prefix("BREAKING-CHANGE:"))
+    {
+        return Some((BREAKING_CHANGE_KEY.to_string(), note.trim().to_string()));
+    }
+
+    let (key, value) = match line.split_once(": ") {
+        Some((key, value)) => (key, value.trim().to_string()),
+        None => {
+            let (key, number) = line.split_once(" #")?;
+            if !ISSUE_CLOSING_KEYS.contains(&key) {
+                return None;
+            }
+            (key, format!("#{}", number.trim()))
+        }
+    };
+
+    let valid_key = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    valid_key.then(|| (key.to_string(), value))
+}
+
+/// 规范化尾注值，纯数字的 Issue 编号补全为 `#N`
+fn normalize_footer_value(key: &str, value: &str) -> String {
+    let is_issue_key = key == "Refs" || ISSUE_CLOSING_KEYS.contains(&key);
+    if is_issue_key && !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) {
+        format!("#{}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// 交互式输入一条新的尾注
+///
+/// # 返回值
+/// 返回 (键, 值)，用户未输入内容时返回None
+fn input_footer() -> Result<Option<(String, String)>> {
+    let mut items: Vec<&str> = COMMON_FOOTER_KEYS.to_vec();
+    items.push("自定义 (Key: value)");
+
+    let selection = select_option("请选择尾注类型", &items)?;
+    let key = match COMMON_FOOTER_KEYS.get(selection) {
+        Some(key) => key.to_string(),
+        None => {
+            // 尾注键名中的空白替换为 `-`，与 Conventional Commits 规范一致
+            let key = get_input("请输入尾注键名: ")?;
+            key.split_whitespace().collect::<Vec<_>>().join("-")
+        }
+    };
+    if key.is_empty() {
+        return Ok(None);
+    }
+
+    let value = get_input(&format!("请输入 {} 的值: ", key))?;
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some((key.clone(), normalize_footer_value(&key, &value))))
 }
 
 /// 编辑提交标注内容
 fn edit_commit_content(content: &mut CommitContent) -> Result<bool> {
     println!("{}", "当前提交标注内容:".bright_yellow());
-    println!("{} {}", "标题:".bright_cyan(), content.title);
+    println!("{} {}", "标题:".bright_cyan(), content.title());
+    println!("{} {}", "作用域:".bright_cyan(), content.scope.as_deref().unwrap_or("无"));
+    let breaking = match content.breaking.as_deref() {
+        Some("") => "是".to_string(),
+        Some(note) => format!("是 ({})", note),
+        None => "否".to_string(),
+    };
+    println!("{} {}", "破坏性变更:".bright_cyan(), breaking);
+    let issue = match &content.issue {
+        Some(issue) if content.issue_in_title => format!("{} (标题)", issue),
+        Some(issue) => format!("{} (Refs 尾注)", issue),
+        None => "无".to_string(),
+    };
+    println!("{} {}", "关联 Issue:".bright_cyan(), issue);
     
-    if !content.content_lines.is_empty() {
+    let body_lines = content.body_lines();
+    if !body_lines.is_empty() {
         println!("{}", "正文:".bright_cyan());
-        for line in content.content_lines.iter() {
+        for line in body_lines.iter() {
             println!("  {}", line);
         }
     }
     
+    if !content.footers.is_empty() {
+        println!("{}", "尾注:".bright_cyan());
+        for (key, value) in content.footers.iter() {
+            println!("  {}", format_footer(key, value));
+        }
+    }
+    
     println!();
     println!("{}", "请选择要编辑的部分:".bright_yellow());
-    println!("  0. 返回不修改");
-    println!("  1. 编辑标题");
-    
-    let max_option = content.content_lines.len() + 3;
     
+    // gitmoji 选择器模式的标题中没有作用域
+    let mut actions = vec![("编辑标题".to_string(), EditAction::Title)];
+    if content.gitmoji_mode == GitmojiMode::Picker {
+        actions.push(("更换 gitmoji".to_string(), EditAction::Gitmoji));
+    } else {
+        actions.push(("编辑作用域".to_string(), EditAction::Scope));
+    }
+    actions.push(("编辑破坏性变更标记".to_string(), EditAction::Breaking));
+    actions.push(("编辑关联 Issue".to_string(), EditAction::Issue));
+    if content.issue.is_some() {
+        let placement = if content.issue_in_title { "将 Issue 编号移到 Refs 尾注" } else { "将 Issue 编号移到标题" };
+        actions.push((placement.to_string(), EditAction::IssuePlacement));
+    }
+    for (i, field) in content.fields.iter().enumerate() {
+        actions.push((format!("编辑 {}", field.label), EditAction::Field(i)));
+    }
     for i in 0..content.content_lines.len() {
-        println!("  {}. 编辑正文第{}行", i + 2, i + 1);
+        actions.push((format!("编辑正文第{}行", i + 1), EditAction::Line(i)));
+    }
+    actions.push(("添加新的正文行".to_string(), EditAction::AddLine));
+    if !content.content_lines.is_empty() {
+        actions.push(("删除最后一行正文".to_string(), EditAction::RemoveLastLine));
     }
+    for (i, (key, _)) in content.footers.iter().enumerate() {
+        actions.push((format!("编辑尾注第{}条 ({})", i + 1, key), EditAction::Footer(i)));
+    }
+    actions.push(("添加尾注 (Refs、Closes、Reviewed-by 等)".to_string(), EditAction::AddFooter));
     
-    println!("  {}. 添加新的正文行", content.content_lines.len() + 2);
-    println!("  {}. 删除最后一行正文", content.content_lines.len() + 3);
+    println!("  0. 返回不修改");
+    for (i, (label, _)) in actions.iter().enumerate() {
+        println!("  {}. {}", i + 1, label);
+    }
     
-    let choice = get_input(&format!("请输入选择 (0-{}): ", max_option))?;
+    let choice = get_input(&format!("请输入选择 (0-{}): ", actions.len()))?;
     let choice = choice.parse::<usize>().unwrap_or(0);
     
-    if choice == 0 {
+    let Some((_, action)) = choice.checked_sub(1).and_then(|i| actions.get(i)) else {
         return Ok(false);
-    } else if choice == 1 {
-        // 编辑标题
-        println!("{} {}", "当前标题:".bright_cyan(), content.title);
-        let new_title = get_input("请输入新标题: ")?;
-        if !new_title.is_empty() {
-            content.title = new_title;
-            println!("{}", "标题已更新".bright_green());
-        }
-        return Ok(true);
-    } else if choice >= 2 && choice <= content.content_lines.len() + 1 {
-        // 编辑现有正文行
-        let line_index = choice - 2;
-        let current_line = &content.content_lines[line_index];
-        let line_content = current_line.trim_start_matches("- ");
-        
-        println!("{} {}", "当前内容:".bright_cyan(), line_content);
-        let new_content = get_input("请输入新内容: ")?;
-        
-        if !new_content.is_empty() {
-            content.content_lines[line_index] = format!("- {}", new_content);
-            println!("{}", "正文已更新".bright_green());
-        }
-        return Ok(true);
-    } else if choice == content.content_lines.len() + 2 {
-        // 添加新的正文行
-        let new_content = get_input("请输入新的正文行: ")?;
-        if !new_content.is_empty() {
-            content.content_lines.push(format!("- {}", new_content));
-            println!("{}", "已添加新的正文行".bright_green());
-        }
-        return Ok(true);
-    } else if choice == content.content_lines.len() + 3 && !content.content_lines.is_empty() {
-        // 删除最后一行正文
-        content.content_lines.pop();
-        println!("{}", "已删除最后一行正文".bright_green());
-        return Ok(true);
+    };
+    
+    let previous_type = content.commit_type.clone();
+    
+    match action {
+        EditAction::Title => {
+            // 编辑标题，输入完整的 `type(scope): subject` 时同时更新类型和作用域
+            println!("{} {}", "当前标题:".bright_cyan(), content.title());
+            let new_title = get_input("请输入新标题: ")?;
+            if !new_title.is_empty() {
+                match parse_conventional_header(&new_title) {
+                    Some(header) => {
+                        content.gitmoji = header.gitmoji.or_else(|| crate::gitmoji::for_type(&header.commit_type));
+                        content.commit_type = header.commit_type;
+                        content.scope = header.scope;
+                        content.subject = header.description;
+                        if !header.breaking {
+                            content.breaking = None;
+                        } else if content.breaking.is_none() {
+                            content.breaking = Some(String::new());
+                        }
+                    }
+                    None => content.subject = new_title,
+                }
+                println!("{}", "标题已更新".bright_green());
+            }
+        }
+        EditAction::Gitmoji => {
+            let gitmoji = select_gitmoji(content.gitmoji.map(|g| g.code))?;
+            content.commit_type = gitmoji.commit_type.to_string();
+            content.gitmoji = Some(gitmoji);
+            println!("{}", "gitmoji 已更新".bright_green());
+        }
+        EditAction::Scope => {
+            content.scope = select_scope(content.scope.as_deref())?;
+            println!("{}", "作用域已更新".bright_green());
+        }
+        EditAction::Breaking => {
+            // 输入说明即标记为破坏性变更，留空则取消标记
+            if let Some(note) = &content.breaking {
+                println!("{} {}", "当前说明:".bright_cyan(), if note.is_empty() { "无" } else { note });
+            }
+            let note = get_input("请输入破坏性变更说明 (留空取消标记): ")?;
+            if note.is_empty() {
+                content.breaking = None;
+                println!("{}", "已取消破坏性变更标记".bright_green());
+            } else {
+                content.breaking = Some(note);
+                println!("{}", "已标记为破坏性变更".bright_green());
+            }
+        }
+        EditAction::Issue => {
+            // 修改关联的 Issue 编号，留空则移除
+            if let Some(issue) = &content.issue {
+                println!("{} {}", "当前 Issue:".bright_cyan(), issue);
+            }
+            let issue = get_input("请输入 Issue 编号 (留空移除): ")?;
+            if issue.is_empty() {
+                content.issue = None;
+                println!("{}", "已移除关联 Issue".bright_green());
+            } else {
+                if content.issue.is_none() {
+                    content.issue_in_title = crate::issue::mode()? == crate::issue::IssueMode::Prefix;
+                }
+                content.issue = Some(issue);
+                println!("{}", "关联 Issue 已更新".bright_green());
+            }
+        }
+        EditAction::IssuePlacement => {
+            content.issue_in_title = !content.issue_in_title;
+            println!("{}", "Issue 编号位置已更新".bright_green());
+        }
+        EditAction::Field(field_index) => {
+            // 编辑模板字段，必填字段不能清空
+            let field = &mut content.fields[*field_index];
+            println!("{} {}", format!("当前{}:", field.label).bright_cyan(), field.value);
+            let hint = if field.required { "" } else { " (输入 - 清空)" };
+            let new_value = get_input(&format!("请输入新内容{}: ", hint))?;
+
+            if !field.required && new_value == "-" {
+                field.value.clear();
+                println!("{}", format!("已清空{}", field.label).bright_green());
+            } else if !new_value.is_empty() {
+                field.value = new_value;
+                println!("{}", format!("{}已更新", field.label).bright_green());
+            }
+        }
+        EditAction::Line(line_index) => {
+            // 编辑现有正文行
+            let current_line = &content.content_lines[*line_index];
+            let line_content = current_line.trim_start_matches("- ");
+            
+            println!("{} {}", "当前内容:".bright_cyan(), line_content);
+            let new_content = get_input("请输入新内容: ")?;
+            
+            if !new_content.is_empty() {
+                content.content_lines[*line_index] = format!("- {}", new_content);
+                println!("{}", "正文已更新".bright_green());
+            }
+        }
+        EditAction::AddLine => {
+            // 添加新的正文行
+            let new_content = get_input("请输入新的正文行: ")?;
+            if !new_content.is_empty() {
+                content.content_lines.push(format!("- {}", new_content));
+                println!("{}", "已添加新的正文行".bright_green());
+            }
+        }
+        EditAction::RemoveLastLine => {
+            // 删除最后一行正文
+            content.content_lines.pop();
+            println!("{}", "已删除最后一行正文".bright_green());
+        }
+        EditAction::Footer(footer_index) => {
+            // 编辑现有尾注，留空则删除
+            let (key, value) = &content.footers[*footer_index];
+            println!("{} {}", "当前尾注:".bright_cyan(), format_footer(key, value));
+            let new_value = get_input(&format!("请输入 {} 的新值 (留空删除该尾注): ", key))?;
+
+            if new_value.is_empty() {
+                content.footers.remove(*footer_index);
+                println!("{}", "已删除尾注".bright_green());
+            } else {
+                let key = key.clone();
+                content.footers[*footer_index].1 = normalize_footer_value(&key, &new_value);
+                println!("{}", "尾注已更新".bright_green());
+            }
+        }
+        EditAction::AddFooter => {
+            if let Some(footer) = input_footer()? {
+                content.footers.push(footer);
+                println!("{}", "已添加尾注".bright_green());
+            }
+        }
+    }
+    
+    // 类型变化后按新类型的模板整理正文
+    if content.commit_type != previous_type {
+        content.apply_template()?;
+        content.fill_fields(true)?;
     }
     
-    Ok(false)
+    Ok(true)
 }
 
-/// 获取多行输入作为提交标注
+/// 收集作用域候选项，按来源依次为配置、最近提交历史和变更文件所在的顶层目录
+///
+/// # 返回值
+/// 返回去重后的 (作用域, 来源说明) 列表
+fn scope_suggestions() -> Result<Vec<(String, &'static str)>> {
+    let mut suggestions: Vec<(String, &'static str)> = Vec::new();
+    let mut push = |scope: String, source: &'static str| {
+        if !scope.is_empty() && !suggestions.iter().any(|(s, _)| *s == scope) {
+            suggestions.push((scope, source));
+        }
+    };
+
+    // 1. 配置中的作用域列表 (git config --add og.scope <作用域>)
+    for scope in crate::config::get_all("og.scope")? {
+        push(scope, "配置");
+    }
+
+    // 2. 最近提交中使用过的作用域，按使用次数排序；空仓库没有提交历史
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for commit in crate::git::list_commits("HEAD", 200).unwrap_or_default() {
+        let Some(scope) = parse_conventional_header(&commit.subject).and_then(|h| h.scope) else {
+            continue;
+        };
+        match counts.iter_mut().find(|(s, _)| *s == scope) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((scope, 1)),
+        }
+    }
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    for (scope, _) in counts {
+        push(scope, "历史");
+    }
+
+    // 3. 变更文件所在的顶层目录
+    for path in crate::git::changed_files()? {
+        if let Some((dir, _)) = path.split_once('/') {
+            push(dir.trim_start_matches('.').to_string(), "变更目录");
+        }
+    }
+
+    Ok(suggestions)
+}
+
+/// 交互式选择提交作用域，支持输入文字筛选
 ///
 /// # 参数
-/// * `default_title` - 可选的默认标题
-/// 
+/// * `current` - 当前的作用域，作为默认选中项
+///
 /// # 返回值
-/// 返回格式化后的提交标注字符串
-use dialoguer::Select;
+/// 返回选择的作用域，选择不使用作用域时返回None
+pub fn select_scope(current: Option<&str>) -> Result<Option<String>> {
+    let mut suggestions = scope_suggestions()?;
+    if let Some(current) = current {
+        if !suggestions.iter().any(|(s, _)| s == current) {
+            suggestions.insert(0, (current.to_string(), "当前"));
+        }
+    }
 
-pub fn get_multiline_commit_message(default_title: Option<String>) -> Result<String> {
-    let mut commit_content = CommitContent {
-        title: String::new(),
-        content_lines: Vec::new(),
-    };
+    let mut items = vec![
+        "不使用作用域".bright_black().to_string(),
+        "手动输入作用域".bright_cyan().to_string(),
+    ];
+    items.extend(
+        suggestions
+            .iter()
+            .map(|(scope, source)| format!("{:<20} {}", scope, source.bright_black())),
+    );
+
+    let default = current
+        .and_then(|c| suggestions.iter().position(|(s, _)| s == c))
+        .map_or(0, |i| i + 2);
+
+    let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("请选择作用域 (输入以筛选)")
+        .items(&items)
+        .default(default)
+        .max_length(15)
+        .interact()
+        .context("无法获取用户选择")?;
+
+    match selection {
+        0 => Ok(None),
+        1 => {
+            let scope = get_input("请输入作用域: ")?;
+            Ok((!scope.is_empty()).then_some(scope))
+        }
+        i => Ok(Some(suggestions[i - 2].0.clone())),
+    }
+}
+
+/// 交互式选择提交类型
+///
+/// # 参数
+/// * `default_type` - 默认选中的类型，不在类型列表中时选中第一项
+///
+/// # 返回值
+/// 返回选择的类型字符串
+pub fn select_commit_type(default_type: Option<&str>) -> Result<&'static str> {
+    let default_index = default_type
+        .and_then(|t| COMMIT_TYPES.iter().position(|(val, _, _)| *val == t))
+        .unwrap_or(0);
 
-    // 1. 选择提交类型
     let selection = Select::with_theme(&ColorfulTheme::default())
         .with_prompt("请选择提交类型")
-        .items(&COMMIT_TYPES.iter().map(|(val, desc)| format!("{:<10} - {}", val, desc)).collect::<Vec<_>>())
-        .default(0)
+        .items(&COMMIT_TYPES.iter().map(|(val, desc, _)| format!("{:<10} - {}", val, desc)).collect::<Vec<_>>())
+        .default(default_index)
         .interact()
         .context("无法获取用户选择")?;
-    let commit_type = COMMIT_TYPES[selection].0;
 
-    // 2. 输入简短描述 (如果命令行没有提供)
-    let subject = if let Some(title) = default_title {
-        title
-    } else {
-        let mut subj = String::new();
-        while subj.is_empty() {
-            subj = get_input("请输入简短描述: ")?;
-            if subj.is_empty() {
-                println!("{}", "简短描述不能为空，请重新输入。".bright_red());
-            }
-        }
-        subj
-    };
+    Ok(COMMIT_TYPES[selection].0)
+}
 
-    // 3. 组合标题
-    let title = format!("{}: {}", commit_type, subject);
-    commit_content.title = title;
-    
-    println!("{}", "请输入提交正文内容（每行一条，直接回车结束）".bright_yellow());
-    
-    let mut line_index = 1;
-    
-    loop {
-        let prompt = format!("正文第{}行: ", line_index);
-        let line = get_input(&prompt)?;
-        
-        if line.is_empty() {
-            break;
-        }
-        
-        commit_content.content_lines.push(format!("- {}", line));
-        line_index += 1;
+/// 交互式选择 gitmoji，支持输入文字筛选
+///
+/// # 参数
+/// * `default_code` - 默认选中的短代码，不在列表中时选中第一项
+///
+/// # 返回值
+/// 返回选择的 gitmoji
+pub fn select_gitmoji(default_code: Option<&str>) -> Result<&'static Gitmoji> {
+    let default_index = default_code
+        .and_then(|code| GITMOJIS.iter().position(|g| g.code == code))
+        .unwrap_or(0);
+
+    let items: Vec<String> = GITMOJIS
+        .iter()
+        .map(|g| format!("{} {:<22} {} ({})", g.emoji, g.code, g.description, g.commit_type))
+        .collect();
+
+    let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("请选择 gitmoji (输入以筛选)")
+        .items(&items)
+        .default(default_index)
+        .max_length(15)
+        .interact()
+        .context("无法获取用户选择")?;
+
+    Ok(&GITMOJIS[selection])
+}
+
+/// 选择提交类型和作用域，以提交标注中的现有值为默认值
+///
+/// gitmoji 选择器模式下以选择 gitmoji 代替选择类型，并且不询问作用域。
+fn select_type_and_scope(content: &mut CommitContent) -> Result<()> {
+    if content.gitmoji_mode == GitmojiMode::Picker {
+        let gitmoji = select_gitmoji(content.gitmoji.map(|g| g.code))?;
+        content.commit_type = gitmoji.commit_type.to_string();
+        content.gitmoji = Some(gitmoji);
+        return Ok(());
     }
-    
-    // 编辑循环
+
+    let default_type = (!content.commit_type.is_empty()).then_some(content.commit_type.as_str());
+    content.commit_type = select_commit_type(default_type)?.to_string();
+    content.scope = select_scope(content.scope.as_deref())?;
+
+    Ok(())
+}
+
+/// 预览并循环编辑提交标注，直到用户确认
+fn preview_and_edit(commit_content: &mut CommitContent) -> Result<()> {
     loop {
         // 显示当前内容
-        let current_message = format_commit_content(&commit_content);
+        let current_message = format_commit_content(commit_content);
         
         println!();
         println!("{}", "提交标注预览:".bright_yellow());
@@ -214,7 +727,7 @@ pub fn get_multiline_commit_message(default_title: Option<String>) -> Result<Str
         
         if edit_option {
             // 编辑内容
-            let edited = edit_commit_content(&mut commit_content)?;
+            let edited = edit_commit_content(commit_content)?;
             
             // 如果内容已编辑，继续循环；否则退出
             if !edited {
@@ -225,6 +738,201 @@ pub fn get_multiline_commit_message(default_title: Option<String>) -> Result<Str
             break;
         }
     }
+
+    Ok(())
+}
+
+/// 从合作者名单中多选本次提交的合作者
+///
+/// # 返回值
+/// 返回 `Name <email>` 形式的身份列表，名单为空时返回空列表
+fn select_co_authors() -> Result<Vec<String>> {
+    let roster = crate::config::co_authors()?;
+    if roster.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let items: Vec<String> = roster
+        .iter()
+        .map(|(handle, identity)| format!("{:<12} {}", handle.bright_yellow(), identity))
+        .collect();
+    let selections = multi_select("请选择本次提交的合作者 (不选直接回车)", &items)?;
+
+    Ok(selections.into_iter().map(|i| roster[i].1.clone()).collect())
+}
+
+/// 将合作者设置为提交标注的 `Co-authored-by` 尾注，替换已有的合作者尾注
+fn set_co_authors(content: &mut CommitContent, co_authors: Vec<String>) {
+    content.footers.retain(|(key, _)| key != CO_AUTHOR_KEY);
+    content
+        .footers
+        .extend(co_authors.into_iter().map(|identity| (CO_AUTHOR_KEY.to_string(), identity)));
+}
+
+/// 构建系统相关的文件名
+const BUILD_FILES: &[&str] = &[
+    "Cargo.toml", "Cargo.lock", "build.rs", "package.json", "package-lock.json", "yarn.lock",
+    "pnpm-lock.yaml", "Makefile", "CMakeLists.txt", "Dockerfile", "go.mod", "go.sum", "pom.xml",
+    "build.gradle", "build.gradle.kts", "settings.gradle", "pyproject.toml", "requirements.txt", "setup.py",
+];
+
+/// CI 配置所在的目录或文件
+const CI_PATHS: &[&str] = &[
+    ".github/workflows/", ".gitlab-ci.yml", ".circleci/", "Jenkinsfile", ".travis.yml",
+    "azure-pipelines.yml", ".drone.yml",
+];
+
+/// 列出与提交类型相关的变更文件：build 为构建文件，ci 为CI配置文件
+///
+/// # 参数
+/// * `commit_type` - 提交类型
+///
+/// # 返回值
+/// 返回变更文件路径列表，其他类型返回空列表
+fn type_related_files(commit_type: &str) -> Result<Vec<String>> {
+    let is_related: fn(&str) -> bool = match commit_type {
+        "build" => |path| BUILD_FILES.contains(&path.rsplit('/').next().unwrap_or(path)),
+        "ci" => |path| CI_PATHS.iter().any(|ci_path| path.starts_with(ci_path)),
+        _ => return Ok(Vec::new()),
+    };
+
+    Ok(crate::git::changed_files()?
+        .into_iter()
+        .filter(|path| is_related(path))
+        .collect())
+}
+
+/// 为 revert 类型选择被回退的提交，自动填写简短描述和 `This reverts commit` 正文
+fn fill_revert_target(content: &mut CommitContent) -> Result<()> {
+    // 空仓库没有提交历史，其他错误照常返回
+    let commits = if crate::git::rev_parse("HEAD").is_ok() {
+        crate::git::list_commits("HEAD", 50)?
+    } else {
+        Vec::new()
+    };
+    if commits.is_empty() {
+        println!("{}", "[WARNING] 仓库中还没有可以回退的提交".bright_yellow());
+        return Ok(());
+    }
+
+    let index = select_commit("请选择要回退的提交", &commits)?;
+    let commit = &commits[index];
+
+    content.subject = commit.subject.clone();
+    content.content_lines.push(format!("- This reverts commit {}.", commit.hash));
+    println!("{}", format!("[INFO] 已根据提交 {} 填写简短描述和正文", commit.short_hash).bright_blue());
+
+    Ok(())
+}
+
+/// 为 fix 类型询问修复的 Issue 编号，可留空
+///
+/// 纯数字编号生成 `Closes #N` 尾注，其他编号 (例如 PROJ-123) 按 `og.issue.mode` 作为关联 Issue。
+fn ask_fixed_issue(content: &mut CommitContent) -> Result<()> {
+    let issue = get_input("请输入修复的 Issue 编号 (可留空): ")?;
+    let issue = issue.trim_start_matches('#');
+    if issue.is_empty() {
+        return Ok(());
+    }
+
+    if issue.chars().all(|c| c.is_ascii_digit()) {
+        content.footers.push(("Closes".to_string(), format!("#{}", issue)));
+    } else {
+        content.issue_in_title = crate::issue::mode()? == crate::issue::IssueMode::Prefix;
+        content.issue = Some(issue.to_string());
+    }
+
+    Ok(())
+}
+
+/// 获取多行输入作为提交标注
+///
+/// # 参数
+/// * `default_title` - 可选的默认标题
+/// * `co_authors` - 通过 `--pair` 指定的合作者，为None时从合作者名单中选择
+/// 
+/// # 返回值
+/// 返回格式化后的提交标注字符串
+pub fn get_multiline_commit_message(default_title: Option<String>, co_authors: Option<Vec<String>>) -> Result<String> {
+    let mut commit_content = CommitContent::new()?;
+
+    // 1. 选择提交类型 (或 gitmoji) 和作用域
+    select_type_and_scope(&mut commit_content)?;
+
+    // 2. revert 类型选择被回退的提交，自动填写简短描述和正文
+    if commit_content.commit_type == "revert" {
+        fill_revert_target(&mut commit_content)?;
+    }
+
+    // 3. 输入简短描述 (如果命令行没有提供且没有自动填写)
+    if let Some(title) = default_title {
+        commit_content.subject = title;
+    }
+    while commit_content.subject.is_empty() {
+        commit_content.subject = get_input("请输入简短描述: ")?;
+        if commit_content.subject.is_empty() {
+            println!("{}", "简短描述不能为空，请重新输入。".bright_red());
+        }
+    }
+    
+    // 4. 按提交类型的模板填写正文字段
+    commit_content.apply_template()?;
+    if !commit_content.fields.is_empty() {
+        println!("{}", format!("请按 {} 模板填写正文", commit_content.commit_type).bright_yellow());
+        commit_content.fill_fields(false)?;
+    }
+    
+    // 5. build 和 ci 类型以变更的构建或CI文件作为默认正文
+    let related_files = type_related_files(&commit_content.commit_type)?;
+    if !related_files.is_empty() {
+        println!("{}", "[INFO] 已将变更的相关文件作为默认正文:".bright_blue());
+        for path in related_files {
+            println!("  - {}", path);
+            commit_content.content_lines.push(format!("- {}", path));
+        }
+    }
+    
+    if commit_content.body_lines().is_empty() {
+        println!("{}", "请输入提交正文内容（每行一条，直接回车结束）".bright_yellow());
+    } else {
+        println!("{}", "请输入其他正文内容（每行一条，直接回车结束）".bright_yellow());
+    }
+    
+    let mut line_index = commit_content.content_lines.len() + 1;
+    
+    loop {
+        let prompt = format!("正文第{}行: ", line_index);
+        let line = get_input(&prompt)?;
+        
+        if line.is_empty() {
+            break;
+        }
+        
+        commit_content.content_lines.push(format!("- {}", line));
+        line_index += 1;
+    }
+    
+    // 从分支名称中提取 Issue 编号
+    if let Some(issue) = crate::issue::branch_issue()? {
+        println!("{}", format!("[INFO] 从分支名称中识别到 Issue 编号: {}", issue).bright_blue());
+        commit_content.issue_in_title = crate::issue::mode()? == crate::issue::IssueMode::Prefix;
+        commit_content.issue = Some(issue);
+    }
+    
+    // fix 类型在分支名称中没有 Issue 编号时询问修复的 Issue
+    if commit_content.commit_type == "fix" && commit_content.issue.is_none() {
+        ask_fixed_issue(&mut commit_content)?;
+    }
+    
+    // 添加合作者
+    let co_authors = match co_authors {
+        Some(co_authors) => co_authors,
+        None => select_co_authors()?,
+    };
+    set_co_authors(&mut commit_content, co_authors);
+    
+    // 编辑循环
+    preview_and_edit(&mut commit_content)?;
     
     // 格式化最终内容
     let result = format_commit_content(&commit_content);
@@ -232,19 +940,153 @@ pub fn get_multiline_commit_message(default_title: Option<String>) -> Result<Str
     Ok(result)
 }
 
-/// 格式化提交内容为字符串
+/// 将已有的提交消息解析为提交标注内容
+///
+/// # 参数
+/// * `message` - 完整的提交消息
+///
+/// # 返回值
+/// 返回解析后的内容，标题不符合 `type(scope): subject` 格式时类型为空字符串
+fn parse_commit_message(message: &str) -> Result<CommitContent> {
+    // 去掉由CI选项追加的 [skip ci] 标记，后续流程会重新询问
+    let message = strip_skip_ci(message);
+
+    let mut lines = message.lines();
+    let title = lines.next().unwrap_or("").trim();
+
+    let mut content = CommitContent::new()?;
+    match parse_conventional_header(title) {
+        Some(header) => {
+            content.gitmoji = header.gitmoji.or_else(|| crate::gitmoji::for_type(&header.commit_type));
+            content.commit_type = header.commit_type;
+            content.scope = header.scope;
+            content.subject = header.description;
+            content.breaking = header.breaking.then(String::new);
+        }
+        None => content.subject = title.to_string(),
+    }
+
+    // 空行之后的最后一段全部为尾注时，解析为结构化的尾注
+    let body: Vec<&str> = lines.collect();
+    let trailer_start = body
+        .iter()
+        .rposition(|line| line.trim().is_empty())
+        .map(|index| index + 1)
+        .filter(|&start| {
+            start < body.len() && body[start..].iter().all(|line| parse_trailer(line).is_some())
+        })
+        .unwrap_or(body.len());
+
+    content.content_lines = body[..trailer_start]
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| format!("- {}", line.trim_start_matches("- ")))
+        .collect();
+
+    for (key, value) in body[trailer_start..].iter().filter_map(|line| parse_trailer(line)) {
+        if key == BREAKING_CHANGE_KEY {
+            content.breaking = Some(value);
+        } else {
+            content.footers.push((key, value));
+        }
+    }
+
+    Ok(content)
+}
+
+/// 以已有的提交消息为基础重新编辑提交标注
+///
+/// # 参数
+/// * `message` - 原提交消息 (通常为HEAD的提交消息)
+/// * `co_authors` - 通过 `--pair` 指定的合作者，指定时替换原有的合作者
+///
+/// # 返回值
+/// 返回格式化后的提交标注字符串
+pub fn edit_existing_commit_message(message: &str, co_authors: Option<Vec<String>>) -> Result<String> {
+    let mut commit_content = parse_commit_message(message)?;
+    if let Some(co_authors) = co_authors {
+        set_co_authors(&mut commit_content, co_authors);
+    }
+
+    // 1. 选择提交类型 (或 gitmoji) 和作用域，默认选中原提交的值
+    select_type_and_scope(&mut commit_content)?;
+
+    // 2. 修改简短描述，默认保留原描述
+    if let Some(subject) = input_with_default("请输入简短描述", &commit_content.subject)? {
+        commit_content.subject = subject;
+    }
+
+    // 3. 按提交类型的模板整理正文 (不强制补填已有提交缺少的必填字段，可在编辑菜单中填写)
+    commit_content.apply_template()?;
+
+    // 4. 进入编辑循环修改正文和尾注
+    preview_and_edit(&mut commit_content)?;
+
+    Ok(format_commit_content(&commit_content))
+}
+
+/// 格式化提交内容为字符串，尾注与正文之间以空行分隔
 fn format_commit_content(content: &CommitContent) -> String {
-    if content.content_lines.is_empty() {
-        return content.title.clone();
-    } else {
-        let mut result = String::new();
-        _ = write!(result, "{}", content.title);
-        
-        for line in &content.content_lines {
+    let mut result = content.title();
+    
+    for line in content.body_lines() {
+        _ = write!(result, "\n{}", line);
+    }
+    
+    let footer_lines = content.footer_lines();
+    if !footer_lines.is_empty() {
+        result.push('\n');
+        for line in footer_lines {
             _ = write!(result, "\n{}", line);
         }
-        
-        result
+    }
+    
+    result
+}
+
+/// 去掉提交消息中由CI选项追加的 `[skip ci]` 标记
+///
+/// # 参数
+/// * `message` - 提交消息
+///
+/// # 返回值
+/// 返回去掉标记后的提交消息
+pub fn strip_skip_ci(message: &str) -> String {
+    message
+        .lines()
+        .map(|line| line.strip_suffix("[skip ci]").unwrap_or(line).trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+/// 为提交消息追加 `[skip ci]` 标记
+///
+/// 消息以尾注段落结尾时，标记追加到尾注之前的最后一行，以免破坏尾注格式。
+///
+/// # 参数
+/// * `message` - 提交消息
+///
+/// # 返回值
+/// 返回追加标记后的提交消息
+pub fn append_skip_ci(message: &str) -> String {
+    let lines: Vec<&str> = message.lines().collect();
+    let trailer_start = lines
+        .iter()
+        .rposition(|line| line.trim().is_empty())
+        .filter(|&blank| {
+            blank > 0 && lines[blank + 1..].iter().all(|line| parse_trailer(line).is_some())
+        });
+
+    match trailer_start {
+        Some(blank) => {
+            let mut result = lines[..blank].join("\n");
+            _ = write!(result, " [skip ci]\n{}", lines[blank..].join("\n"));
+            result
+        }
+        None => format!("{} [skip ci]", message),
     }
 }
 
@@ -275,3 +1117,418 @@ pub fn select_reset_mode() -> Result<String> {
     
     Ok(RESET_MODES[selection].0.to_string())
 }
+
+/// 交互式选择一个提交
+///
+/// # 参数
+/// * `prompt` - 提示消息
+/// * `commits` - 候选提交列表
+///
+/// # 返回值
+/// 返回选中提交在列表中的索引
+pub fn select_commit(prompt: &str, commits: &[crate::git::CommitInfo]) -> Result<usize> {
+    let items: Vec<String> = commits
+        .iter()
+        .map(|c| format!("{} {} ({}, {})", c.short_hash.bright_yellow(), c.subject, c.author, c.date))
+        .collect();
+
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .items(&items)
+        .default(0)
+        .interact()
+        .context("无法获取用户选择")?;
+
+    Ok(selection)
+}
+
+/// 交互式选择一个储藏
+///
+/// # 参数
+/// * `prompt` - 提示消息
+/// * `entries` - 候选储藏列表
+///
+/// # 返回值
+/// 返回选中储藏在列表中的索引
+pub fn select_stash(prompt: &str, entries: &[crate::git::StashEntry]) -> Result<usize> {
+    let items: Vec<String> = entries
+        .iter()
+        .map(|e| format!("{} {} ({})", e.reference.bright_yellow(), e.message, e.date))
+        .collect();
+
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .items(&items)
+        .default(0)
+        .interact()
+        .context("无法获取用户选择")?;
+
+    Ok(selection)
+}
+
+/// 将简短描述转换为分支名可用的片段
+///
+/// 转为小写，空白和非法字符替换为 `-`，并去除首尾及重复的 `-` 和 `.`，
+/// 以及结尾的 `.lock` (引用名不允许出现 `..` 或以 `.lock` 结尾)。
+/// 非ASCII字符 (例如中文) 会被保留。
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+
+    for c in text.trim().chars() {
+        if c.is_alphanumeric() || c == '_' {
+            slug.extend(c.to_lowercase());
+        } else if c == '.' {
+            if !slug.ends_with('.') {
+                slug.push('.');
+            }
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    let mut slug = slug.as_str();
+    loop {
+        let trimmed = slug.trim_matches(|c| c == '-' || c == '.');
+        match trimmed.strip_suffix(".lock") {
+            Some(rest) => slug = rest,
+            None => break trimmed.to_string(),
+        }
+    }
+}
+
+/// 交互式模糊搜索选择一个分支
+///
+/// # 参数
+/// * `prompt` - 提示消息
+/// * `branches` - 候选分支列表
+///
+/// # 返回值
+/// 返回选中分支在列表中的索引
+pub fn fuzzy_select_branch(prompt: &str, branches: &[crate::git::BranchInfo]) -> Result<usize> {
+    let items: Vec<String> = branches
+        .iter()
+        .map(|b| {
+            let kind = if b.is_remote { "[远程]" } else { "[本地]" };
+            format!("{} {} ({}, {})", kind, b.name, b.subject, b.date)
+        })
+        .collect();
+
+    let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .items(&items)
+        .default(0)
+        .interact()
+        .context("无法获取用户选择")?;
+
+    Ok(selection)
+}
+
+/// 交互式多选
+///
+/// # 参数
+/// * `prompt` - 提示消息
+/// * `items` - 候选项列表
+///
+/// # 返回值
+/// 返回选中项的索引列表，按列表顺序排列
+pub fn multi_select(prompt: &str, items: &[String]) -> Result<Vec<usize>> {
+    let selections = MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("{} (空格选择，回车确认)", prompt))
+        .items(items)
+        .interact()
+        .context("无法获取用户选择")?;
+
+    Ok(selections)
+}
+
+/// 交互式单选
+///
+/// # 参数
+/// * `prompt` - 提示消息
+/// * `items` - 候选项列表
+///
+/// # 返回值
+/// 返回选中项的索引
+pub fn select_option(prompt: &str, items: &[&str]) -> Result<usize> {
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .items(items)
+        .default(0)
+        .interact()
+        .context("无法获取用户选择")?;
+
+    Ok(selection)
+}
+
+/// Conventional Commits 标题的组成部分
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionalHeader {
+    /// 提交类型 (例如: feat)
+    pub commit_type: String,
+    /// 影响范围 (例如: parser)
+    pub scope: Option<String>,
+    /// 是否带有破坏性变更标记 `!`
+    pub breaking: bool,
+    /// 简短描述
+    pub description: String,
+    /// 标题开头的 gitmoji
+    pub gitmoji: Option<&'static Gitmoji>,
+}
+
+/// 解析 `type(scope)!: description` 格式的提交标题
+///
+/// # 参数
+/// * `subject` - 提交标题
+///
+/// # 返回值
+/// 返回解析结果，不符合格式或类型不在 COMMIT_TYPES 中时返回None；
+/// 支持开头带 gitmoji 的 `✨ feat: subject` 和只有 gitmoji 的 `:sparkles: subject` 形式
+pub fn parse_conventional_header(subject: &str) -> Option<ConventionalHeader> {
+    // 开头的 gitmoji 不影响解析，例如 `✨ feat: subject`
+    let (gitmoji, subject) = crate::gitmoji::strip_prefix(subject.trim());
+
+    let parsed = subject.split_once(": ").and_then(|(header, description)| {
+        let (header, breaking) = match header.strip_suffix('!') {
+            Some(header) => (header, true),
+            None => (header, false),
+        };
+
+        let (commit_type, scope) = match header.split_once('(') {
+            Some((commit_type, scope)) => (commit_type, Some(scope.strip_suffix(')')?.to_string())),
+            None => (header, None),
+        };
+
+        if !COMMIT_TYPES.iter().any(|(val, _, _)| *val == commit_type) {
+            return None;
+        }
+
+        Some(ConventionalHeader {
+            commit_type: commit_type.to_string(),
+            scope,
+            breaking,
+            description: description.trim().to_string(),
+            gitmoji,
+        })
+    });
+
+    // 只有 gitmoji 没有类型时 (例如 `:sparkles: subject`)，按 gitmoji 对应的类型解析
+    parsed.or_else(|| {
+        let gitmoji = gitmoji?;
+        Some(ConventionalHeader {
+            commit_type: gitmoji.commit_type.to_string(),
+            scope: None,
+            breaking: false,
+            description: subject.to_string(),
+            gitmoji: Some(gitmoji),
+        })
+    })
+}
+
+/// 高亮提交标题中的 Conventional Commits 类型
+///
+/// # 参数
+/// * `subject` - 提交标题
+///
+/// # 返回值
+/// 类型部分着色后的标题，不符合 `type: subject` 格式时原样返回
+pub fn highlight_commit_type(subject: &str) -> String {
+    let Some(parsed) = parse_conventional_header(subject) else {
+        return subject.to_string();
+    };
+    let Some((header, rest)) = subject.split_once(": ") else {
+        return subject.to_string();
+    };
+
+    let colored_header = match parsed.commit_type.as_str() {
+        "feat" => header.bright_green(),
+        "fix" => header.bright_red(),
+        "perf" => header.bright_magenta(),
+        "revert" => header.red(),
+        "docs" | "style" | "test" => header.bright_blue(),
+        _ => header.bright_cyan(),
+    };
+
+    format!("{}: {}", colored_header, rest)
+}
+
+/// 交互式选择回退目标提交，支持输入文字筛选
+///
+/// # 参数
+/// * `commits` - 候选提交列表 (从HEAD开始)
+/// * `first_parent` - 沿第一父提交从HEAD开始的提交哈希，下标即 `HEAD~n` 的距离
+/// * `unpushed` - 尚未推送的提交哈希集合
+///
+/// # 返回值
+/// 返回选中提交在列表中的索引，用户选择手动输入时返回None
+pub fn select_reset_target(
+    commits: &[crate::git::CommitInfo],
+    first_parent: &[String],
+    unpushed: &std::collections::HashSet<String>,
+) -> Result<Option<usize>> {
+    let mut items = vec!["手动输入回退目标 (例如: HEAD~1 或 commit hash)".bright_cyan().to_string()];
+    items.extend(commits.iter().map(|c| {
+        let status = if unpushed.contains(&c.hash) {
+            "未推送".bright_green()
+        } else {
+            "已推送".bright_red()
+        };
+        // 经由合并的第二父提交引入的提交没有 HEAD~n 形式的引用
+        let label = match first_parent.iter().position(|hash| *hash == c.hash) {
+            Some(distance) => format!("HEAD~{:<3}", distance),
+            None => " ".repeat(8),
+        };
+        format!(
+            "{} {:<8} {} {} ({}, {})",
+            label.bright_black(),
+            c.short_hash.bright_yellow(),
+            status,
+            highlight_commit_type(&c.subject),
+            c.author,
+            c.date
+        )
+    }));
+
+    // 默认选中 HEAD~1
+    let default = first_parent
+        .get(1)
+        .and_then(|parent| commits.iter().position(|c| c.hash == *parent))
+        .map_or(0, |index| index + 1);
+
+    let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("请选择回退目标 (输入以筛选)")
+        .items(&items)
+        .default(default)
+        .max_length(15)
+        .interact()
+        .context("无法获取用户选择")?;
+
+    Ok(selection.checked_sub(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picker_content(scope: Option<&str>, breaking: Option<&str>) -> CommitContent {
+        CommitContent {
+            commit_type: "feat".to_string(),
+            scope: scope.map(str::to_string),
+            subject: "支持注释".to_string(),
+            fields: Vec::new(),
+            content_lines: Vec::new(),
+            breaking: breaking.map(str::to_string),
+            footers: Vec::new(),
+            issue: None,
+            issue_in_title: false,
+            gitmoji_mode: GitmojiMode::Picker,
+            gitmoji: crate::gitmoji::for_type("feat"),
+        }
+    }
+
+    #[test]
+    fn picker_title_keeps_scope_and_breaking_marker() {
+        assert_eq!(picker_content(None, None).title(), ":sparkles: 支持注释");
+        assert_eq!(picker_content(Some("parser"), None).title(), ":sparkles: feat(parser): 支持注释");
+        assert_eq!(picker_content(Some("parser"), Some("")).title(), ":sparkles: feat(parser)!: 支持注释");
+
+        let header = parse_conventional_header(&picker_content(None, Some("")).title()).unwrap();
+        assert!(header.breaking);
+        assert_eq!(header.commit_type, "feat");
+    }
+
+    #[test]
+    fn parses_conventional_headers() {
+        let header = parse_conventional_header("feat(parser)!: 支持注释").unwrap();
+        assert_eq!(header.commit_type, "feat");
+        assert_eq!(header.scope.as_deref(), Some("parser"));
+        assert!(header.breaking);
+        assert_eq!(header.description, "支持注释");
+        assert!(header.gitmoji.is_none());
+
+        assert!(parse_conventional_header("unknown: 支持注释").is_none());
+        assert!(parse_conventional_header("feat(parser: 支持注释").is_none());
+        assert!(parse_conventional_header("支持注释").is_none());
+    }
+
+    #[test]
+    fn parses_gitmoji_headers() {
+        for title in ["✨ feat: 支持注释", ":sparkles: feat: 支持注释"] {
+            let header = parse_conventional_header(title).unwrap();
+            assert_eq!(header.commit_type, "feat");
+            assert_eq!(header.description, "支持注释");
+            assert_eq!(header.gitmoji.map(|g| g.code), Some(":sparkles:"));
+        }
+
+        // 只有 gitmoji 时按其对应的类型解析
+        let header = parse_conventional_header(":bug: 修复崩溃").unwrap();
+        assert_eq!(header.commit_type, "fix");
+        assert_eq!(header.description, "修复崩溃");
+
+        // 表情末尾的变体选择符可有可无
+        for title in ["♻️ refactor(core)!: 拆分模块", "♻ refactor(core)!: 拆分模块"] {
+            let header = parse_conventional_header(title).unwrap();
+            assert_eq!(header.commit_type, "refactor");
+            assert_eq!(header.scope.as_deref(), Some("core"));
+            assert!(header.breaking);
+        }
+    }
+
+    #[test]
+    fn slugify_produces_valid_ref_components() {
+        assert_eq!(slugify("Fix Login Page"), "fix-login-page");
+        assert_eq!(slugify("a..b"), "a.b");
+        assert_eq!(slugify("bump x.lock"), "bump-x");
+        assert_eq!(slugify("x.lock.lock."), "x");
+        assert_eq!(slugify("  --登录 页面--  "), "登录-页面");
+    }
+
+    #[test]
+    fn parses_trailer_lines() {
+        assert_eq!(parse_trailer("Refs: PROJ-12"), Some(("Refs".to_string(), "PROJ-12".to_string())));
+        assert_eq!(parse_trailer("Closes #12"), Some(("Closes".to_string(), "#12".to_string())));
+        assert_eq!(
+            parse_trailer("BREAKING CHANGE: 移除旧接口"),
+            Some((BREAKING_CHANGE_KEY.to_string(), "移除旧接口".to_string()))
+        );
+
+        // 只有关闭 Issue 的键接受 `Key #N` 形式
+        assert!(parse_trailer("Issue #3").is_none());
+        assert!(parse_trailer("PR #5").is_none());
+        assert!(parse_trailer("Fixed in PR #5").is_none());
+        assert!(parse_trailer("修复崩溃").is_none());
+    }
+
+    #[test]
+    fn skip_ci_marker_round_trips() {
+        assert_eq!(append_skip_ci("fix: 修复崩溃"), "fix: 修复崩溃 [skip ci]");
+        assert_eq!(strip_skip_ci("fix: 修复崩溃 [skip ci]"), "fix: 修复崩溃");
+
+        // 以尾注结尾时标记加在尾注之前，尾注保持不变
+        let message = "fix: 修复崩溃\n- 空指针检查\n\nCloses #12\nRefs: PROJ-3";
+        let marked = append_skip_ci(message);
+        assert_eq!(marked, "fix: 修复崩溃\n- 空指针检查 [skip ci]\n\nCloses #12\nRefs: PROJ-3");
+        assert_eq!(strip_skip_ci(&marked), message);
+
+        // 正文中的 `Issue #3` 不是尾注，标记追加到末尾
+        assert_eq!(append_skip_ci("fix: 修复崩溃\n\nIssue #3"), "fix: 修复崩溃\n\nIssue #3 [skip ci]");
+    }
+
+    #[test]
+    fn commit_message_footers_round_trip() {
+        let message = "feat(parser)!: 支持注释\n- 新增注释语法\n\nBREAKING CHANGE: 移除旧语法\nCloses #12\nRefs: PROJ-3";
+        let mut content = parse_commit_message(message).unwrap();
+        content.gitmoji_mode = GitmojiMode::Off;
+
+        assert_eq!(content.breaking.as_deref(), Some("移除旧语法"));
+        assert_eq!(
+            content.footers,
+            vec![("Closes".to_string(), "#12".to_string()), ("Refs".to_string(), "PROJ-3".to_string())]
+        );
+        assert_eq!(format_commit_content(&content), message);
+
+        // 最后一段不全是尾注时保留为正文
+        let content = parse_commit_message("fix: 修复崩溃\n\nIssue #3").unwrap();
+        assert!(content.footers.is_empty());
+        assert_eq!(content.content_lines, vec!["- Issue #3".to_string()]);
+    }
+}